@@ -9,6 +9,7 @@ extern crate "glm_color" as color;
 use color::*;
 use test::Bencher;
 use rand::{ IsaacRng, Rng };
+use std::mem;
 
 macro_rules! bench_to_rgb(
     ($name: ident, $t: ty) => {
@@ -56,6 +57,50 @@ macro_rules! bench_from_rgb(
     }
 );
 
+// The slice benches convert `LEN` colors per iteration, so the time per
+// pixel is `ns/iter` divided by `LEN`. `MB/s` is the throughput of the
+// source colors.
+
+macro_rules! bench_to_rgb_slice(
+    ($name: ident, $t: ty) => {
+        #[bench]
+        fn $name(bh: &mut Bencher) {
+            const LEN: usize = 1 << 13;
+
+            let mut rng = IsaacRng::new_unseeded();
+
+            let elems: Vec<$t> = (0..LEN).map(|_| rng.gen::<$t>()).collect();
+            let mut dst = vec![Rgb::new(0., 0., 0.); LEN];
+
+            bh.bytes = (LEN * mem::size_of::<$t>()) as u64;
+            bh.iter(|| {
+                <$t as ColorSpace>::to_rgb_slice(&elems, &mut dst);
+                test::black_box(&dst);
+            })
+        }
+    }
+);
+
+macro_rules! bench_from_rgb_slice(
+    ($name: ident, $t: ty, $ret: ident) => {
+        #[bench]
+        fn $name(bh: &mut Bencher) {
+            const LEN: usize = 1 << 13;
+
+            let mut rng = IsaacRng::new_unseeded();
+
+            let elems: Vec<$t> = (0..LEN).map(|_| rng.gen::<$t>()).collect();
+            let mut dst: Vec<$ret> = (0..LEN).map(|_| rng.gen::<$ret>()).collect();
+
+            bh.bytes = (LEN * mem::size_of::<$t>()) as u64;
+            bh.iter(|| {
+                <$ret as ColorSpace>::from_rgb_slice(&elems, &mut dst);
+                test::black_box(&dst);
+            })
+        }
+    }
+);
+
 bench_to_rgb!(_hsv2rgb, Hsv);
 bench_from_rgb!(_rgb2hsv, Rgb, Hsv);
 
@@ -64,3 +109,89 @@ bench_from_rgb!(_rgb2srgb, Rgb, Srgb);
 
 bench_to_rgb!(_ycbcr2rgb, YCbCr);
 bench_from_rgb!(_rgb2ycbcr, Rgb, YCbCr);
+
+bench_to_rgb!(_xyz2rgb, Xyz);
+bench_from_rgb!(_rgb2xyz, Rgb, Xyz);
+
+bench_to_rgb_slice!(_hsv2rgb_slice, Hsv);
+bench_from_rgb_slice!(_rgb2hsv_slice, Rgb, Hsv);
+
+bench_to_rgb_slice!(_srgb2rgb_slice, Srgb);
+bench_from_rgb_slice!(_rgb2srgb_slice, Rgb, Srgb);
+
+bench_to_rgb_slice!(_ycbcr2rgb_slice, YCbCr);
+bench_from_rgb_slice!(_rgb2ycbcr_slice, Rgb, YCbCr);
+
+bench_to_rgb_slice!(_xyz2rgb_slice, Xyz);
+bench_from_rgb_slice!(_rgb2xyz_slice, Rgb, Xyz);
+
+// 8-bit sRGB, with `pow` and with the lookup tables.
+
+#[bench]
+fn _srgb8_to_rgb_pow(bh: &mut Bencher) {
+    const LEN: usize = 1 << 13;
+
+    let mut rng = IsaacRng::new_unseeded();
+
+    let elems: Vec<(u8, u8, u8)> = (0..LEN).map(|_| rng.gen()).collect();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (LEN - 1);
+
+        let (r, g, b) = unsafe { *elems.get_unchecked(i) };
+        test::black_box(Srgb::new(r as f32 / 255., g as f32 / 255., b as f32 / 255.).to_rgb());
+    })
+}
+
+#[bench]
+fn _srgb8_to_rgb_table(bh: &mut Bencher) {
+    const LEN: usize = 1 << 13;
+
+    let mut rng = IsaacRng::new_unseeded();
+
+    let elems: Vec<(u8, u8, u8)> = (0..LEN).map(|_| rng.gen()).collect();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (LEN - 1);
+
+        let (r, g, b) = unsafe { *elems.get_unchecked(i) };
+        test::black_box(Rgb::from_srgb_u8(r, g, b));
+    })
+}
+
+#[bench]
+fn _rgb_to_srgb8_pow(bh: &mut Bencher) {
+    const LEN: usize = 1 << 13;
+
+    let mut rng = IsaacRng::new_unseeded();
+
+    let elems: Vec<Rgb> = (0..LEN).map(|_| rng.gen::<Rgb>()).collect();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (LEN - 1);
+
+        let e = unsafe { elems.get_unchecked(i) };
+        let s = from_rgb::<Srgb>(*e);
+        test::black_box(Rgb::new(s.red(), s.green(), s.blue()).to_u8());
+    })
+}
+
+#[bench]
+fn _rgb_to_srgb8_table(bh: &mut Bencher) {
+    const LEN: usize = 1 << 13;
+
+    let mut rng = IsaacRng::new_unseeded();
+
+    let elems: Vec<Rgb> = (0..LEN).map(|_| rng.gen::<Rgb>()).collect();
+    let mut i = 0;
+
+    bh.iter(|| {
+        i = (i + 1) & (LEN - 1);
+
+        let e = unsafe { elems.get_unchecked(i) };
+        test::black_box(e.to_srgb_u8());
+    })
+}
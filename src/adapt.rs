@@ -0,0 +1,215 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Chromatic adaptation transforms.
+//!
+//! A chromatic adaptation transform (CAT) predicts the color that looks the
+//! same under a different illuminant. XYZ values are converted to a cone
+//! response like space, scaled by the ratio of the responses of the
+//! destination and source white points, and converted back to XYZ.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate glm;
+//! # extern crate glm_color;
+//! # fn main() {
+//! use glm::*;
+//! use glm_color::*;
+//! use glm_color::adapt::Cat;
+//! use glm_color::xyz::{ D50, D65 };
+//!
+//! // a color on a D65 screen, and on D50 paper.
+//! let screen: Xyz = from_rgb(CORNFLOWER_BLUE);
+//! let paper = screen.adapt(D65, D50, Cat::Bradford);
+//! assert!(D65.adapt(D65, D50, Cat::Cat16).is_close_to(&D50, 0.00001));
+//!
+//! // a reusable matrix, for converting many colors.
+//! let m = Cat::Bradford.matrix(D65, D50);
+//! assert!((m * *screen.as_vec3()).is_close_to(paper.as_vec3(), 0.00001));
+//! # }
+//! ```
+//!
+//! # See
+//!
+//! - [Chromatic Adaptation](http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html)
+//!   in Bruce Lindbloom's site.
+//! - Li et al., _Comprehensive color solutions: CAM16, CAT16, and CAM16-UCS_,
+//!   Color Research & Application, 2017.
+
+use glm::*;
+use super::xyz::Xyz;
+
+/// Chromatic adaptation transforms.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Cat {
+    /// The Bradford transform, used by ICC profiles.
+    Bradford,
+    /// The von Kries transform, with the Hunt-Pointer-Estevez cone
+    /// fundamentals normalized to D65.
+    VonKries,
+    /// Scaling XYZ values directly. It is the least accurate one.
+    XyzScaling,
+    /// The transform of CIECAM02.
+    Cat02,
+    /// The transform of CAM16.
+    Cat16,
+}
+
+impl Cat {
+    // Rows of the matrix from XYZ to the cone response space.
+    fn rows(&self) -> [[f64; 3]; 3] {
+        match *self {
+            Cat::Bradford => [
+                [ 0.8951,  0.2664, -0.1614],
+                [-0.7502,  1.7135,  0.0367],
+                [ 0.0389, -0.0685,  1.0296]
+            ],
+            Cat::VonKries => [
+                [ 0.40024, 0.70760, -0.08081],
+                [-0.22630, 1.16532,  0.04570],
+                [ 0.,      0.,       0.91822]
+            ],
+            Cat::XyzScaling => [
+                [1., 0., 0.],
+                [0., 1., 0.],
+                [0., 0., 1.]
+            ],
+            Cat::Cat02 => [
+                [ 0.7328, 0.4296, -0.1624],
+                [-0.7036, 1.6975,  0.0061],
+                [ 0.0030, 0.0136,  0.9834]
+            ],
+            Cat::Cat16 => [
+                [ 0.401288, 0.650173, -0.051461],
+                [-0.250268, 1.204414,  0.045854],
+                [-0.002079, 0.048952,  0.953127]
+            ],
+        }
+    }
+
+    // The cone response matrix, in double precision.
+    fn cone(&self) -> DMat3 {
+        let r = self.rows();
+        // column major.
+        dmat3(
+            r[0][0], r[1][0], r[2][0],
+            r[0][1], r[1][1], r[2][1],
+            r[0][2], r[1][2], r[2][2]
+        )
+    }
+
+    /// Returns the matrix that converts XYZ values to the cone response
+    /// space of _self_.
+    #[inline]
+    pub fn cone_matrix(&self) -> Mat3 {
+        let r = self.rows();
+        let c = |j: usize| -> Vec3 {
+            vec3(r[0][j] as f32, r[1][j] as f32, r[2][j] as f32)
+        };
+        Mat3 { c0: c(0), c1: c(1), c2: c(2) }
+    }
+
+    /// Returns the matrix that adapts XYZ values from white point `src` to
+    /// white point `dst`.
+    ///
+    /// The matrix is computed in double precision. Compute it once for
+    /// converting many colors.
+    ///
+    /// # Panics
+    ///
+    /// If any cone response of `src` is `0`.
+    pub fn matrix(&self, src: Xyz, dst: Xyz) -> Mat3 {
+        let m = self.cone();
+        let w = |clr: Xyz| -> DVec3 {
+            m * dvec3(clr.x() as f64, clr.y() as f64, clr.z() as f64)
+        };
+        let (s, d) = (w(src), w(dst));
+        assert!(s.x != 0. && s.y != 0. && s.z != 0., "source white has no response.");
+        let scale = d / s;
+        let scaled = DMat3 { c0: m.c0 * scale, c1: m.c1 * scale, c2: m.c2 * scale };
+        let a = inverse(&m) * scaled;
+        let c = |i: usize| -> Vec3 { vec3(a[i].x as f32, a[i].y as f32, a[i].z as f32) };
+        Mat3 { c0: c(0), c1: c(1), c2: c(2) }
+    }
+}
+
+impl Xyz {
+    /// Adapts _self_, which is seen under white point `src`, to white point
+    /// `dst`, using transform `cat`.
+    ///
+    /// To adapt many colors, multiply them by `cat.matrix(src, dst)`
+    /// instead.
+    #[inline]
+    pub fn adapt(&self, src: Xyz, dst: Xyz, cat: Cat) -> Xyz {
+        let v = cat.matrix(src, dst) * *self.as_vec3();
+        Xyz::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use space::from_rgb;
+    use xyz::{ Xyz, D50, D65 };
+    use super::Cat;
+    use super::Cat::*;
+    use quickcheck::*;
+
+    const CATS: [Cat; 5] = [Bradford, VonKries, XyzScaling, Cat02, Cat16];
+
+    #[test]
+    fn test_white() {
+        for &cat in CATS.iter() {
+            assert!(D65.adapt(D65, D50, cat).is_close_to(&D50, 0.00001));
+            assert!(D50.adapt(D50, D65, cat).is_close_to(&D65, 0.00001));
+            let m = cat.matrix(D65, D65);
+            let id = mat3(1., 0., 0., 0., 1., 0., 0., 0., 1.);
+            assert!((0..3).all(|i| m[i].is_close_to(&id[i], 0.000001)));
+        }
+    }
+
+    #[test]
+    fn test_bradford() {
+        // from CSS Color Module Level 4, which uses the same white points.
+        let published = mat3(
+             1.0479298, 0.0296278, -0.0092430,
+             0.0229469, 0.9904344,  0.0150552,
+            -0.0501923, -0.0170738, 0.7518743
+        );
+        let m = Bradford.matrix(D65, D50);
+        assert!((0..3).all(|i| m[i].is_close_to(&published[i], 0.000001)));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        fn prop(clr: Rgb) -> bool {
+            let xyz: Xyz = from_rgb(clr);
+            CATS.iter().all(|&cat| {
+                xyz.adapt(D65, D50, cat).adapt(D50, D65, cat).is_close_to(&xyz, 0.0001)
+            })
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+}
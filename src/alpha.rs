@@ -0,0 +1,233 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use super::space::{ ColorSpace, from_rgb };
+use super::rgb::Rgb;
+use super::hsv::Hsv;
+use super::hsl::Hsl;
+use super::hwb::Hwb;
+use super::hsi::Hsi;
+use super::ycbcr::YCbCr;
+use super::srgb::Srgb;
+use super::xyz::{ Xyz, Xyy };
+use super::lab::Lab;
+use super::lch::Lch;
+use super::oklab::{ Oklab, Oklch };
+use super::working::{ DisplayP3, AdobeRgb, Rec2020, ProPhotoRgb, AcesCg, Aces2065 };
+use std::mem;
+use rand::{ Rand, Rng };
+
+/// A color value in color space `C` with an alpha channel.
+///
+/// Alpha is the opacity of the color. It is a value in the interval
+/// _[0, 1]_, where `0` is fully transparent and `1` is fully opaque. The
+/// color channels are not premultiplied by alpha.
+///
+/// Conversions between color spaces keep the alpha value unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let red = Rgba::new(RED, 0.5);
+/// let hsva: Hsva = red.convert();
+/// assert_eq!(hsva.alpha(), 0.5);
+/// assert_eq!(hsva.to_rgba(), red);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Alpha<C> {
+    color: C,
+    alpha: f32
+}
+
+/// Linear RGB color with alpha.
+pub type Rgba = Alpha<Rgb>;
+
+/// HSV color with alpha.
+pub type Hsva = Alpha<Hsv>;
+
+impl<C: ColorSpace + Copy> Alpha<C> {
+    /// Constructs an `Alpha` value from `color` and `alpha`.
+    ///
+    /// Parameter `alpha` is clampped to the interval _[0, 1]_.
+    #[inline]
+    pub fn new(color: C, alpha: f32) -> Alpha<C> {
+        Alpha { color: color, alpha: clamp(alpha, 0., 1.) }
+    }
+
+    /// Constructs a fully opaque `Alpha` value from `color`.
+    #[inline]
+    pub fn opaque(color: C) -> Alpha<C> {
+        Alpha { color: color, alpha: 1. }
+    }
+
+    /// Returns the color of _self_, without alpha.
+    #[inline]
+    pub fn color(&self) -> C {
+        self.color
+    }
+
+    /// Returns the alpha value of _self_.
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Changes _self_'s color to `color`.
+    #[inline]
+    pub fn set_color(&mut self, color: C) {
+        self.color = color;
+    }
+
+    /// Returns a new `Alpha` value with given `color`, and alpha value from
+    /// _self_.
+    #[inline]
+    pub fn with_color(&self, color: C) -> Alpha<C> {
+        Alpha { color: color, alpha: self.alpha }
+    }
+
+    /// Changes _self_'s alpha value to `a`.
+    ///
+    /// The parameter `a` is clampped to the range [0, 1].
+    #[inline]
+    pub fn set_alpha(&mut self, a: f32) {
+        self.alpha = clamp(a, 0., 1.);
+    }
+
+    /// Returns a new `Alpha` value with given alpha value `a`, and color from
+    /// _self_.
+    #[inline]
+    pub fn with_alpha(&self, a: f32) -> Alpha<C> {
+        let mut c = *self;
+        c.set_alpha(a);
+        c
+    }
+
+    /// Constructs from color value `rgba` in RGB color space with alpha.
+    #[inline]
+    pub fn from_rgba(rgba: Rgba) -> Alpha<C> {
+        Alpha { color: from_rgb(rgba.color), alpha: rgba.alpha }
+    }
+
+    /// Converts _self_ to a color value in RGB color space with alpha.
+    #[inline]
+    pub fn to_rgba(&self) -> Rgba {
+        Alpha { color: self.color.to_rgb(), alpha: self.alpha }
+    }
+
+    /// Converts _self_ to color space `D`, keeping the alpha value.
+    #[inline]
+    pub fn convert<D: ColorSpace>(&self) -> Alpha<D> {
+        Alpha { color: from_rgb(self.color.to_rgb()), alpha: self.alpha }
+    }
+}
+
+macro_rules! impl_as_vec4(
+    ($($t: ty),+) => {
+        $(
+            impl Alpha<$t> {
+                /// Re-interprets a reference of `Alpha` to `Vec4`. The alpha
+                /// value is the last component.
+                #[inline]
+                pub fn as_vec4(&self) -> &Vec4 {
+                    let v: &Vec4 = unsafe { mem::transmute(self) };
+                    v
+                }
+            }
+        )+
+    }
+);
+
+impl_as_vec4! {
+    Rgb, Hsv, Hsl, Hwb, Hsi, YCbCr, Srgb, Xyz, Xyy, Lab, Lch, Oklab, Oklch,
+    DisplayP3, AdobeRgb, Rec2020, ProPhotoRgb, AcesCg, Aces2065
+}
+
+impl<C: Eq> Eq for Alpha<C> {}
+
+impl<C: ApproxEq<BaseType = f32>> ApproxEq for Alpha<C> {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Alpha<C>, max_diff: f32) -> bool {
+        self.color.is_close_to(&other.color, max_diff) &&
+        self.alpha.is_close_to(&other.alpha, max_diff)
+    }
+}
+
+impl<C: Rand> Rand for Alpha<C> {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Alpha<C> {
+        let color = rng.gen::<C>();
+        let alpha = rng.gen::<f32>();
+        Alpha { color: color, alpha: alpha }
+    }
+}
+
+/// Constructs a `Rgba` value with given RGBA values `r`, `g`, `b` and `a`.
+///
+/// Equivalent to `Rgba::new(Rgb::from_u8(r, g, b), a / 255)`.
+#[inline]
+pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+    Alpha { color: Rgb::from_u8(r, g, b), alpha: a as f32 / 255. }
+}
+
+/// Equivalent to call `Hsva::new(Hsv::new(h, s, v), a)`.
+#[inline]
+pub fn hsva(h: f32, s: f32, v: f32, a: f32) -> Hsva {
+    Alpha::new(Hsv::new(h, s, v), a)
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use hsv::Hsv;
+    use lab::Lab;
+    use super::{ Alpha, Rgba, Hsva, rgba };
+    use quickcheck::*;
+
+    #[test]
+    fn test_convert() {
+        fn prop(clr: Rgb, a: f32) -> bool {
+            let rgba = Rgba::new(clr, a.fract().abs());
+            let hsva: Hsva = rgba.convert();
+            let laba = Alpha::<Lab>::from_rgba(rgba);
+            hsva.alpha() == rgba.alpha() &&
+            laba.alpha() == rgba.alpha() &&
+            hsva.to_rgba().is_close_to(&rgba, 0.00001) &&
+            laba.to_rgba().is_close_to(&rgba, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb, f32) -> bool);
+    }
+
+    #[test]
+    fn test_as_vec4() {
+        let clr = rgba(255, 0, 0, 0);
+        assert_eq!(*clr.as_vec4(), vec4(1., 0., 0., 0.));
+        let hsva = Alpha::new(Hsv::new(1., 0.5, 0.25), 0.75);
+        assert_eq!(*hsva.as_vec4(), vec4(1., 0.5, 0.25, 0.75));
+    }
+}
@@ -0,0 +1,293 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use super::rgb::Rgb;
+use super::alpha::{ Alpha, Rgba };
+use super::composite::PorterDuff;
+
+/// Blend modes defined in W3C's Compositing and Blending specification.
+///
+/// In the descriptions below, _source_ is the color that is painted, and
+/// _backdrop_ is the color that is painted on.
+///
+/// # Note
+///
+/// The non-separable modes use the luma weights of `Rgb::lunimance()`
+/// (ITU-R BT.709), instead of the weights of BT.601 used in the
+/// specification, because `Rgb` is linear.
+///
+/// # See
+///
+/// - [Compositing and Blending Level 1](http://www.w3.org/TR/compositing-1/#blending).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BlendMode {
+    /// The source.
+    Normal,
+    /// The product of the source and the backdrop.
+    Multiply,
+    /// The complement of the product of the complements of the source and the
+    /// backdrop.
+    Screen,
+    /// `Multiply` or `Screen`, depending on the backdrop.
+    Overlay,
+    /// The darker of the source and the backdrop.
+    Darken,
+    /// The lighter of the source and the backdrop.
+    Lighten,
+    /// Brightens the backdrop to reflect the source.
+    ColorDodge,
+    /// Darkens the backdrop to reflect the source.
+    ColorBurn,
+    /// `Multiply` or `Screen`, depending on the source.
+    HardLight,
+    /// Darkens or lightens the backdrop, depending on the source.
+    SoftLight,
+    /// The absolute difference of the source and the backdrop.
+    Difference,
+    /// Similar to `Difference`, but with lower contrast.
+    Exclusion,
+    /// The hue of the source, with the saturation and luminosity of the
+    /// backdrop.
+    Hue,
+    /// The saturation of the source, with the hue and luminosity of the
+    /// backdrop.
+    Saturation,
+    /// The hue and saturation of the source, with the luminosity of the
+    /// backdrop.
+    Color,
+    /// The luminosity of the source, with the hue and saturation of the
+    /// backdrop.
+    Luminosity,
+}
+
+impl BlendMode {
+    /// Returns `true` if _self_ blends each color channel independently.
+    #[inline]
+    pub fn is_separable(&self) -> bool {
+        !matches!(*self, BlendMode::Hue | BlendMode::Saturation |
+                         BlendMode::Color | BlendMode::Luminosity)
+    }
+
+    // The blending function of a separable mode.
+    #[inline]
+    fn blend_channel(&self, cb: f32, cs: f32) -> f32 {
+        match *self {
+            BlendMode::Normal => cs,
+            BlendMode::Multiply => cb * cs,
+            BlendMode::Screen => cb + cs - cb * cs,
+            BlendMode::Overlay => BlendMode::HardLight.blend_channel(cs, cb),
+            BlendMode::Darken => min(cb, cs),
+            BlendMode::Lighten => max(cb, cs),
+            BlendMode::ColorDodge => {
+                if cb == 0. {
+                    0.
+                } else if cs >= 1. {
+                    1.
+                } else {
+                    min(1., cb / (1. - cs))
+                }
+            }
+            BlendMode::ColorBurn => {
+                if cb >= 1. {
+                    1.
+                } else if cs == 0. {
+                    0.
+                } else {
+                    1. - min(1., (1. - cb) / cs)
+                }
+            }
+            BlendMode::HardLight => {
+                if cs <= 0.5 {
+                    BlendMode::Multiply.blend_channel(cb, 2. * cs)
+                } else {
+                    BlendMode::Screen.blend_channel(cb, 2. * cs - 1.)
+                }
+            }
+            BlendMode::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1. - 2. * cs) * cb * (1. - cb)
+                } else {
+                    let d =
+                        if cb <= 0.25 {
+                            ((16. * cb - 12.) * cb + 4.) * cb
+                        } else {
+                            sqrt(cb)
+                        };
+                    cb + (2. * cs - 1.) * (d - cb)
+                }
+            }
+            BlendMode::Difference => abs(cb - cs),
+            BlendMode::Exclusion => cb + cs - 2. * cb * cs,
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Same weights as `Rgb::lunimance()`.
+#[inline]
+fn lum(c: Vec3) -> f32 {
+    dot(c, vec3(0.2126, 0.7152, 0.0722))
+}
+
+#[inline]
+fn clip_color(c: Vec3) -> Vec3 {
+    let l = lum(c);
+    let n = c.min();
+    let x = c.max();
+    let mut v = c;
+    if n < 0. {
+        v = (v - l) * (l / (l - n)) + l;
+    }
+    if x > 1. {
+        v = (v - l) * ((1. - l) / (x - l)) + l;
+    }
+    v
+}
+
+#[inline]
+fn set_lum(c: Vec3, l: f32) -> Vec3 {
+    clip_color(c + (l - lum(c)))
+}
+
+#[inline]
+fn sat(c: Vec3) -> f32 {
+    c.max() - c.min()
+}
+
+#[inline]
+fn set_sat(c: Vec3, s: f32) -> Vec3 {
+    let n = c.min();
+    let x = c.max();
+    if x > n {
+        (c - n) * (s / (x - n))
+    } else {
+        vec3(0., 0., 0.)
+    }
+}
+
+impl Rgb {
+    /// Blends _self_, the source, with `backdrop` using blend mode `mode`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::blend::BlendMode;
+    ///
+    /// assert_eq!(RED.blend(WHITE, BlendMode::Multiply), RED);
+    /// assert_eq!(RED.blend(BLUE, BlendMode::Screen), MAGENTA);
+    /// assert_eq!(RED.blend(RED, BlendMode::Difference), BLACK);
+    /// ```
+    pub fn blend(&self, backdrop: Rgb, mode: BlendMode) -> Rgb {
+        let cs = *self.as_vec3();
+        let cb = *backdrop.as_vec3();
+        let v =
+            match mode {
+                BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+                BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+                BlendMode::Color => set_lum(cs, lum(cb)),
+                BlendMode::Luminosity => set_lum(cb, lum(cs)),
+                _ => vec3(
+                    mode.blend_channel(cb.x, cs.x),
+                    mode.blend_channel(cb.y, cs.y),
+                    mode.blend_channel(cb.z, cs.z)
+                ),
+            };
+        Rgb::new(v.x, v.y, v.z)
+    }
+}
+
+impl Alpha<Rgb> {
+    /// Blends _self_, the source, with `backdrop` using blend mode `mode`,
+    /// and then composites the result over `backdrop`.
+    ///
+    /// Both _self_ and `backdrop`, and the result, are _not_ premultiplied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::blend::BlendMode;
+    ///
+    /// // blending with a transparent backdrop has no effect.
+    /// let src = Rgba::new(RED, 0.5);
+    /// let dst = Rgba::new(BLUE, 0.);
+    /// assert_eq!(src.blend(dst, BlendMode::Multiply), src);
+    /// ```
+    pub fn blend(&self, backdrop: Rgba, mode: BlendMode) -> Rgba {
+        let ab = backdrop.alpha();
+        let cs = self.color();
+        let blended = cs.blend(backdrop.color(), mode);
+        let v = mix_s(*cs.as_vec3(), *blended.as_vec3(), ab);
+        Alpha::new(Rgb::new(v.x, v.y, v.z), self.alpha())
+            .composite(backdrop, PorterDuff::SourceOver)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use super::BlendMode;
+    use super::BlendMode::*;
+    use quickcheck::*;
+
+    const SEPARABLE: [BlendMode; 12] = [
+        Normal, Multiply, Screen, Overlay, Darken, Lighten, ColorDodge,
+        ColorBurn, HardLight, SoftLight, Difference, Exclusion
+    ];
+
+    #[test]
+    fn test_commutative() {
+        fn prop(a: Rgb, b: Rgb) -> bool {
+            [Multiply, Screen, Darken, Lighten, Difference, Exclusion].iter().all(|&m| {
+                a.blend(b, m).is_close_to(&b.blend(a, m), 0.000001)
+            }) &&
+            a.blend(b, Overlay).is_close_to(&b.blend(a, HardLight), 0.000001)
+        }
+        quickcheck(prop as fn(Rgb, Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_separable() {
+        fn prop(a: Rgb, b: Rgb) -> bool {
+            SEPARABLE.iter().all(|&m| {
+                let c = a.blend(b, m);
+                m.is_separable() &&
+                c.red() == Rgb::new(a.red(), 0., 0.).blend(Rgb::new(b.red(), 0., 0.), m).red()
+            })
+        }
+        quickcheck(prop as fn(Rgb, Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_non_separable() {
+        fn prop(a: Rgb, b: Rgb) -> bool {
+            !Luminosity.is_separable() &&
+            is_close_to(&a.blend(b, Luminosity).lunimance(), &a.lunimance(), 0.0001) &&
+            is_close_to(&a.blend(b, Color).lunimance(), &b.lunimance(), 0.0001)
+        }
+        quickcheck(prop as fn(Rgb, Rgb) -> bool);
+    }
+}
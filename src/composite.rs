@@ -0,0 +1,210 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use super::rgb::Rgb;
+use super::alpha::{ Alpha, Rgba };
+
+/// The Porter-Duff compositing operators.
+///
+/// In the descriptions below, _source_ is the color that is painted, and
+/// _destination_ is the backdrop that is painted on.
+///
+/// # See
+///
+/// - [Compositing and Blending Level 1](http://www.w3.org/TR/compositing-1/#porterduffcompositingoperators).
+/// - Thomas Porter and Tom Duff, _Compositing Digital Images_, SIGGRAPH 1984.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PorterDuff {
+    /// Neither the source nor the destination is shown.
+    Clear,
+    /// Only the source is shown.
+    Source,
+    /// Only the destination is shown.
+    Destination,
+    /// The source is placed over the destination.
+    SourceOver,
+    /// The destination is placed over the source.
+    DestinationOver,
+    /// The part of the source that overlaps the destination is shown.
+    SourceIn,
+    /// The part of the destination that overlaps the source is shown.
+    DestinationIn,
+    /// The part of the source that does not overlap the destination is shown.
+    SourceOut,
+    /// The part of the destination that does not overlap the source is shown.
+    DestinationOut,
+    /// The part of the source that overlaps the destination is placed over
+    /// the destination.
+    SourceAtop,
+    /// The part of the destination that overlaps the source is placed over
+    /// the source.
+    DestinationAtop,
+    /// The parts of the source and the destination that do not overlap are
+    /// shown.
+    Xor,
+}
+
+impl PorterDuff {
+    /// Returns the fractions `(Fa, Fb)` of the source and the destination
+    /// that contribute to the result, given source alpha `sa` and destination
+    /// alpha `da`.
+    ///
+    /// The premultiplied result is `source * Fa + destination * Fb`.
+    #[inline]
+    pub fn factors(&self, sa: f32, da: f32) -> (f32, f32) {
+        match *self {
+            PorterDuff::Clear => (0., 0.),
+            PorterDuff::Source => (1., 0.),
+            PorterDuff::Destination => (0., 1.),
+            PorterDuff::SourceOver => (1., 1. - sa),
+            PorterDuff::DestinationOver => (1. - da, 1.),
+            PorterDuff::SourceIn => (da, 0.),
+            PorterDuff::DestinationIn => (0., sa),
+            PorterDuff::SourceOut => (1. - da, 0.),
+            PorterDuff::DestinationOut => (0., 1. - sa),
+            PorterDuff::SourceAtop => (da, 1. - sa),
+            PorterDuff::DestinationAtop => (1. - da, sa),
+            PorterDuff::Xor => (1. - da, 1. - sa),
+        }
+    }
+}
+
+impl Alpha<Rgb> {
+    /// Returns a copy of _self_ whose color channels are multiplied by the
+    /// alpha value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let red = Rgba::new(RED, 0.5);
+    /// assert_eq!(red.premultiply(), Rgba::new(Rgb::new(0.5, 0., 0.), 0.5));
+    /// ```
+    #[inline]
+    pub fn premultiply(&self) -> Rgba {
+        Alpha::new(self.color() * self.alpha(), self.alpha())
+    }
+
+    /// Returns a copy of _self_, which has premultiplied color channels, whose
+    /// color channels are divided by the alpha value.
+    ///
+    /// Fully transparent colors become transparent black.
+    #[inline]
+    pub fn unpremultiply(&self) -> Rgba {
+        let a = self.alpha();
+        if is_approx_eq(&a, &0.) {
+            Alpha::new(Rgb::new(0., 0., 0.), 0.)
+        } else {
+            let v = *self.color().as_vec3() / a;
+            Alpha::new(Rgb::new(v.x, v.y, v.z), a)
+        }
+    }
+
+    /// Composites _self_, the source, with `dst` using operator `op`.
+    ///
+    /// Both _self_ and `dst`, and the result, are _not_ premultiplied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::composite::PorterDuff;
+    ///
+    /// let src = Rgba::new(RED, 0.5);
+    /// let dst = Rgba::opaque(BLUE);
+    /// let clr = src.composite(dst, PorterDuff::SourceOver);
+    /// assert_eq!(clr, Rgba::opaque(Rgb::new(0.5, 0., 0.5)));
+    /// ```
+    #[inline]
+    pub fn composite(&self, dst: Rgba, op: PorterDuff) -> Rgba {
+        self.premultiply()
+            .composite_premultiplied(dst.premultiply(), op)
+            .unpremultiply()
+    }
+
+    /// Composites _self_, the source, with `dst` using operator `op`.
+    ///
+    /// Both _self_ and `dst`, and the result, are premultiplied.
+    #[inline]
+    pub fn composite_premultiplied(&self, dst: Rgba, op: PorterDuff) -> Rgba {
+        let (sa, da) = (self.alpha(), dst.alpha());
+        let (fa, fb) = op.factors(sa, da);
+        let v = *self.color().as_vec3() * fa + *dst.color().as_vec3() * fb;
+        Alpha::new(Rgb::new(v.x, v.y, v.z), sa * fa + da * fb)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use alpha::Rgba;
+    use super::PorterDuff;
+    use super::PorterDuff::*;
+    use quickcheck::*;
+
+    const OPS: [PorterDuff; 12] = [
+        Clear, Source, Destination, SourceOver, DestinationOver, SourceIn,
+        DestinationIn, SourceOut, DestinationOut, SourceAtop, DestinationAtop,
+        Xor
+    ];
+
+    #[test]
+    fn test_premultiply() {
+        fn prop(clr: Rgb, a: f32) -> bool {
+            let alpha = clamp(a.fract().abs(), 0.01, 1.);
+            let rgba = Rgba::new(clr, alpha);
+            rgba.premultiply().unpremultiply().is_close_to(&rgba, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb, f32) -> bool);
+    }
+
+    #[test]
+    fn test_composite() {
+        fn prop(src: Rgb, dst: Rgb, sa: f32, da: f32) -> bool {
+            let s = Rgba::new(src, clamp(sa.fract().abs(), 0.01, 1.));
+            let d = Rgba::new(dst, clamp(da.fract().abs(), 0.01, 1.));
+            let close = |op: PorterDuff, expected: Rgba| -> bool {
+                s.composite(d, op).is_close_to(&expected, 0.0001)
+            };
+            close(Source, s) &&
+            close(Destination, d) &&
+            close(SourceOver, d.composite(s, DestinationOver)) &&
+            close(SourceAtop, d.composite(s, DestinationAtop)) &&
+            close(Xor, d.composite(s, Xor)) &&
+            OPS.iter().all(|&op| {
+                let a = s.composite(d, op).alpha();
+                a >= 0. && a <= 1.
+            })
+        }
+        quickcheck(prop as fn(Rgb, Rgb, f32, f32) -> bool);
+    }
+
+    #[test]
+    fn test_clear() {
+        let clr = Rgba::opaque(Rgb::new(1., 1., 1.));
+        assert_eq!(clr.composite(clr, Clear), Rgba::new(Rgb::new(0., 0., 0.), 0.));
+    }
+}
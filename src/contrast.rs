@@ -0,0 +1,179 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+
+//! Contrast of text and background colors, as defined in the Web Content
+//! Accessibility Guidelines (WCAG) 2.x.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::*;
+//! use glm_color::contrast::{ Level, TextSize };
+//!
+//! let bg = Rgb::from_srgb_u8(0x33, 0x66, 0x99);
+//! let fg = bg.text_color();
+//! assert_eq!(fg, WHITE);
+//! assert!(fg.contrast_ratio(bg) > 5.);
+//! assert!(fg.is_legible_on(bg, Level::AA, TextSize::Normal));
+//! assert!(!fg.is_legible_on(bg, Level::AAA, TextSize::Normal));
+//! ```
+//!
+//! # See
+//!
+//! - [WCAG 2.1, Contrast (Minimum)](http://www.w3.org/TR/WCAG21/#contrast-minimum).
+//! - [WCAG 2.1, Contrast (Enhanced)](http://www.w3.org/TR/WCAG21/#contrast-enhanced).
+
+use super::rgb::Rgb;
+use super::rgb::consts::{ BLACK, WHITE };
+
+/// The conformance levels of WCAG.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Level {
+    /// Level AA, the minimum contrast.
+    AA,
+    /// Level AAA, the enhanced contrast.
+    AAA,
+}
+
+/// The sizes of text, which have different contrast requirements.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TextSize {
+    /// Text smaller than `TextSize::Large`.
+    Normal,
+    /// Text that is at least 18 points, or 14 points and bold.
+    Large,
+}
+
+impl Level {
+    /// Returns the minimum contrast ratio of text of size `size` required
+    /// by _self_.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::contrast::{ Level, TextSize };
+    ///
+    /// assert_eq!(Level::AA.min_contrast_ratio(TextSize::Normal), 4.5);
+    /// assert_eq!(Level::AAA.min_contrast_ratio(TextSize::Large), 4.5);
+    /// ```
+    #[inline]
+    pub fn min_contrast_ratio(&self, size: TextSize) -> f32 {
+        match (*self, size) {
+            (Level::AA, TextSize::Normal) => 4.5,
+            (Level::AA, TextSize::Large) => 3.,
+            (Level::AAA, TextSize::Normal) => 7.,
+            (Level::AAA, TextSize::Large) => 4.5,
+        }
+    }
+}
+
+impl Rgb {
+    /// Returns the relative luminance of _self_, as defined in WCAG.
+    ///
+    /// Because `Rgb` is linear, it is same as `lunimance()`. WCAG 2.x
+    /// decodes sRGB with a slightly different threshold (`0.03928` instead
+    /// of `0.04045`), which makes no difference for 8-bit colors.
+    #[inline]
+    pub fn relative_luminance(&self) -> f32 {
+        self.lunimance()
+    }
+
+    /// Returns the contrast ratio between _self_ and `other`, which is in the
+    /// range *[1, 21]*.
+    ///
+    /// The ratio is symmetric, i.e., it does not matter which color is the
+    /// text and which is the background.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// assert!((BLACK.contrast_ratio(WHITE) - 21.).abs() < 0.0001);
+    /// assert_eq!(RED.contrast_ratio(RED), 1.);
+    /// ```
+    #[inline]
+    pub fn contrast_ratio(&self, other: Rgb) -> f32 {
+        let a = self.relative_luminance() + 0.05;
+        let b = other.relative_luminance() + 0.05;
+        if a > b { a / b } else { b / a }
+    }
+
+    /// Returns `true` if text of color _self_ and size `size` on background
+    /// `bg` has enough contrast for conformance level `level`.
+    #[inline]
+    pub fn is_legible_on(&self, bg: Rgb, level: Level, size: TextSize) -> bool {
+        self.contrast_ratio(bg) >= level.min_contrast_ratio(size)
+    }
+
+    /// Returns black or white, whichever has the higher contrast ratio with
+    /// _self_ as the background.
+    ///
+    /// The contrast ratio of the result and _self_ is at least `4.58`.
+    #[inline]
+    pub fn text_color(&self) -> Rgb {
+        if BLACK.contrast_ratio(*self) >= WHITE.contrast_ratio(*self) {
+            BLACK
+        } else {
+            WHITE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgb::Rgb;
+    use rgb::consts::{ BLACK, WHITE };
+    use super::{ Level, TextSize };
+    use quickcheck::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        // #777777 is the lightest gray that fails AA on white.
+        let gray = Rgb::from_srgb_u8(0x77, 0x77, 0x77);
+        assert!((gray.contrast_ratio(WHITE) - 4.48).abs() < 0.01);
+        assert!(!gray.is_legible_on(WHITE, Level::AA, TextSize::Normal));
+        assert!(gray.is_legible_on(WHITE, Level::AA, TextSize::Large));
+        let gray = Rgb::from_srgb_u8(0x76, 0x76, 0x76);
+        assert!((gray.contrast_ratio(WHITE) - 4.54).abs() < 0.01);
+        assert!(gray.is_legible_on(WHITE, Level::AA, TextSize::Normal));
+        assert!(gray.is_legible_on(WHITE, Level::AAA, TextSize::Large));
+        assert!(!gray.is_legible_on(WHITE, Level::AAA, TextSize::Normal));
+    }
+
+    #[test]
+    fn test_text_color() {
+        fn prop(bg: Rgb) -> bool {
+            let fg = bg.text_color();
+            let r = fg.contrast_ratio(bg);
+            (fg == BLACK || fg == WHITE) &&
+            r >= 4.58 && r <= 21.0001 &&
+            r == bg.contrast_ratio(fg) &&
+            fg.is_legible_on(bg, Level::AA, TextSize::Normal)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+        assert_eq!(BLACK.text_color(), WHITE);
+        assert_eq!(WHITE.text_color(), BLACK);
+    }
+}
@@ -0,0 +1,979 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Parsing and writing colors in CSS.
+//!
+//! All the color types, with or without `Alpha`, implement `FromStr`, and
+//! accept
+//!
+//! - hexadecimal notations, `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa`,
+//! - functional notations `rgb()`, `rgba()`, `hsl()` and `hsla()`, in both
+//!   the legacy comma separated syntax and the modern space separated syntax,
+//! - functional notations `hwb()`, `lab()`, `lch()`, `oklab()`, `oklch()`
+//!   and `color()` with `srgb`, `srgb-linear`, `display-p3`, `a98-rgb`,
+//!   `prophoto-rgb` or `rec2020`, in the modern syntax,
+//! - color keywords, and `transparent`.
+//!
+//! They also have a `to_css()` method, which writes colors in the notation
+//! chosen by a `Format`. What is written can be parsed back.
+//!
+//! Names of keywords and functions are case-insensitive, and the keywords are
+//! resolved through the table of `names`.
+//!
+//! # Note
+//!
+//! Colors in CSS are in the sRGB color space. They are decoded to the linear
+//! RGB color space, except when parsed to `Srgb`. The channels of the color
+//! constants are treated as sRGB values in keywords, as CSS does, so
+//! `"cornflowerblue"` and `"#6495ed"` are same color, but are different from
+//! `CORNFLOWER_BLUE`.
+//!
+//! `Hsl`, `Hsv` and `Hwb` are computed from linear RGB values, but `hsl()`
+//! and `hwb()` from sRGB values. So their channels, including the hue, are
+//! not the ones in CSS strings, e.g., `"hsl(30deg 100% 50%)"` is parsed to
+//! an `Hsl` value with a hue of about 12.84°, and `Notation::Hsl` writes
+//! the hue of the sRGB values.
+//!
+//! Parsing to a type without alpha ignores the alpha value.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::*;
+//!
+//! let orange: Rgb = "#ff8800".parse().unwrap();
+//! let half: Rgba = "rgb(255 136 0 / 50%)".parse().unwrap();
+//! assert_eq!(half.color(), orange);
+//! assert_eq!(half.alpha(), 0.5);
+//!
+//! let red: Hsv = "hsl(0deg 100% 50%)".parse().unwrap();
+//! assert_eq!(red.to_rgb(), RED);
+//!
+//! let err = "rgb(255, 0 0)".parse::<Rgb>().unwrap_err();
+//! assert_eq!(err.position(), 11);
+//!
+//! let fmt = css::Format::new(css::Notation::Hex);
+//! assert_eq!(orange.to_css(&fmt), "#ff8800");
+//! ```
+//!
+//! # See
+//!
+//! - [CSS Color Module Level 4](http://www.w3.org/TR/css-color-4/).
+
+use glm::*;
+use super::space::{ ColorSpace, from_rgb };
+use super::rgb::Rgb;
+use super::hsv::Hsv;
+use super::hsl::Hsl;
+use super::hwb::Hwb;
+use super::hsi::Hsi;
+use super::ycbcr::YCbCr;
+use super::srgb::Srgb;
+use super::xyz::{ Xyz, Xyy, D50, D65 };
+use super::adapt::Cat;
+use super::lab::Lab;
+use super::lch::Lch;
+use super::oklab::{ Oklab, Oklch };
+use super::working::{ DisplayP3, AdobeRgb, Rec2020, ProPhotoRgb, AcesCg, Aces2065 };
+use super::alpha::Alpha;
+use super::names;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Reasons of failures of parsing CSS colors.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ParseColorErrorKind {
+    /// The string is empty, or contains only white spaces.
+    Empty,
+    /// A hexadecimal notation has a non-hexadecimal digit, or does not have
+    /// 3, 4, 6 or 8 digits.
+    InvalidHex,
+    /// The keyword is not a color name.
+    UnknownKeyword,
+    /// The function is not a CSS color function.
+    UnknownFunction,
+    /// A number is malformed.
+    InvalidNumber,
+    /// The unit of a number is unknown, or not allowed for the argument.
+    InvalidUnit,
+    /// The arguments of a function are too many or too few, or are separated
+    /// wrongly.
+    InvalidArguments,
+    /// The color space of `color()` is not one of `srgb`, `srgb-linear`,
+    /// `display-p3`, `a98-rgb`, `prophoto-rgb` and `rec2020`.
+    UnknownColorSpace,
+    /// An unexpected character is found.
+    UnexpectedCharacter,
+    /// The string ends before the color is complete.
+    UnexpectedEnd,
+}
+
+impl ParseColorErrorKind {
+    fn description(&self) -> &'static str {
+        match *self {
+            ParseColorErrorKind::Empty => "empty color string",
+            ParseColorErrorKind::InvalidHex => "invalid hexadecimal color",
+            ParseColorErrorKind::UnknownKeyword => "unknown color keyword",
+            ParseColorErrorKind::UnknownFunction => "unknown color function",
+            ParseColorErrorKind::UnknownColorSpace => "unknown color space",
+            ParseColorErrorKind::InvalidNumber => "invalid number",
+            ParseColorErrorKind::InvalidUnit => "invalid unit",
+            ParseColorErrorKind::InvalidArguments => "invalid arguments",
+            ParseColorErrorKind::UnexpectedCharacter => "unexpected character",
+            ParseColorErrorKind::UnexpectedEnd => "unexpected end of color string",
+        }
+    }
+}
+
+/// The error returned when parsing a CSS color fails.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ParseColorError {
+    pos: usize,
+    kind: ParseColorErrorKind
+}
+
+impl ParseColorError {
+    /// Returns the byte offset in the parsed string where the error is found.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the reason of the error.
+    #[inline]
+    pub fn kind(&self) -> ParseColorErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind.description(), self.pos)
+    }
+}
+
+impl Error for ParseColorError {
+    fn description(&self) -> &str {
+        self.kind.description()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Unit {
+    Number,
+    Percent,
+    Deg,
+    Rad,
+    Grad,
+    Turn,
+}
+
+// An argument of a color function.
+#[derive(Copy, Clone, Debug)]
+struct Arg {
+    value: f32,
+    unit: Unit,
+    pos: usize
+}
+
+impl Arg {
+    #[inline]
+    fn error(&self) -> ParseColorError {
+        ParseColorError { pos: self.pos, kind: ParseColorErrorKind::InvalidUnit }
+    }
+
+    // A red, green or blue channel, either in [0, 255] or in percentage.
+    fn channel(&self) -> Result<f32, ParseColorError> {
+        match self.unit {
+            Unit::Number => Ok(clamp(self.value / 255., 0., 1.)),
+            Unit::Percent => Ok(clamp(self.value / 100., 0., 1.)),
+            _ => Err(self.error()),
+        }
+    }
+
+    // A number, or a percentage of `reference`.
+    fn number(&self, reference: f32) -> Result<f32, ParseColorError> {
+        match self.unit {
+            Unit::Number => Ok(self.value),
+            Unit::Percent => Ok(self.value * reference / 100.),
+            _ => Err(self.error()),
+        }
+    }
+
+    // A percentage. Numbers are also accepted, as in the modern syntax.
+    fn percentage(&self) -> Result<f32, ParseColorError> {
+        match self.unit {
+            Unit::Number | Unit::Percent => Ok(clamp(self.value / 100., 0., 1.)),
+            _ => Err(self.error()),
+        }
+    }
+
+    // An alpha value, either in [0, 1] or in percentage.
+    fn alpha(&self) -> Result<f32, ParseColorError> {
+        match self.unit {
+            Unit::Number => Ok(clamp(self.value, 0., 1.)),
+            Unit::Percent => Ok(clamp(self.value / 100., 0., 1.)),
+            _ => Err(self.error()),
+        }
+    }
+
+    // A hue, in radians in [0, 2π). Numbers are in degrees.
+    fn hue(&self) -> Result<f32, ParseColorError> {
+        let deg =
+            match self.unit {
+                Unit::Number | Unit::Deg => self.value,
+                Unit::Rad => degrees(self.value),
+                Unit::Grad => self.value * 0.9,
+                Unit::Turn => self.value * 360.,
+                Unit::Percent => return Err(self.error()),
+            };
+        let d = deg % 360.;
+        Ok(radians(if d < 0. { d + 360. } else { d }))
+    }
+}
+
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    fn error(&self, kind: ParseColorErrorKind) -> ParseColorError {
+        ParseColorError { pos: self.pos, kind: kind }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).cloned()
+    }
+
+    #[inline]
+    fn skip_while<F: Fn(u8) -> bool>(&mut self, f: F) -> &'a [u8] {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !f(c) {
+                break;
+            }
+            self.pos += 1;
+        }
+        &self.src[start..self.pos]
+    }
+
+    #[inline]
+    fn skip_spaces(&mut self) {
+        self.skip_while(|c| (c as char).is_whitespace());
+    }
+
+    // Succeeds if only white spaces are left.
+    fn end(&mut self) -> Result<(), ParseColorError> {
+        self.skip_spaces();
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(ParseColorErrorKind::UnexpectedCharacter)),
+        }
+    }
+
+    fn color(&mut self) -> Result<Alpha<Srgb>, ParseColorError> {
+        self.skip_spaces();
+        let clr =
+            match self.peek() {
+                None => return Err(self.error(ParseColorErrorKind::Empty)),
+                Some(b'#') => self.hex()?,
+                Some(c) if (c as char).is_alphabetic() => {
+                    let start = self.pos;
+                    let name = self.skip_while(|c| (c as char).is_alphanumeric() || c == b'-');
+                    let name = String::from_utf8_lossy(name).to_ascii_lowercase();
+                    if self.peek() == Some(b'(') {
+                        self.pos += 1;
+                        self.function(&name, start)?
+                    } else {
+                        keyword(&name).ok_or(ParseColorError {
+                            pos: start, kind: ParseColorErrorKind::UnknownKeyword
+                        })?
+                    }
+                }
+                Some(_) => return Err(self.error(ParseColorErrorKind::UnexpectedCharacter)),
+            };
+        self.end()?;
+        Ok(clr)
+    }
+
+    fn hex(&mut self) -> Result<Alpha<Srgb>, ParseColorError> {
+        let start = self.pos;
+        self.pos += 1;
+        let digits = self.skip_while(|c| c.is_ascii_hexdigit());
+        if let Some(true) = self.peek().map(|c| c.is_ascii_alphanumeric()) {
+            return Err(self.error(ParseColorErrorKind::InvalidHex));
+        }
+        let d: Vec<u8> = digits.iter().map(|&c| {
+            (c as char).to_digit(16).unwrap() as u8
+        }).collect();
+        let v: Vec<u8> =
+            match d.len() {
+                3 | 4 => d.iter().map(|&x| x * 17).collect(),
+                6 | 8 => d.chunks(2).map(|x| x[0] * 16 + x[1]).collect(),
+                _ => return Err(ParseColorError {
+                    pos: start, kind: ParseColorErrorKind::InvalidHex
+                }),
+            };
+        // same as the color constants.
+        let c = Rgb::from_u8(v[0], v[1], v[2]);
+        let a = if v.len() == 4 { v[3] as f32 / 255. } else { 1. };
+        Ok(Alpha::new(Srgb::new(c.red(), c.green(), c.blue()), a))
+    }
+
+    fn function(&mut self, name: &str, start: usize) -> Result<Alpha<Srgb>, ParseColorError> {
+        let legacy_allowed =
+            match name {
+                "rgb" | "rgba" | "hsl" | "hsla" => true,
+                "hwb" | "lab" | "lch" | "oklab" | "oklch" | "color" => false,
+                _ => return Err(ParseColorError {
+                    pos: start, kind: ParseColorErrorKind::UnknownFunction
+                }),
+            };
+        let space =
+            if name == "color" {
+                self.skip_spaces();
+                let pos = self.pos;
+                let id = self.skip_while(|c| c.is_ascii_alphanumeric() || c == b'-');
+                let id = String::from_utf8_lossy(id).to_ascii_lowercase();
+                match &*id {
+                    "srgb" | "srgb-linear" | "display-p3" |
+                    "a98-rgb" | "prophoto-rgb" | "rec2020" => id,
+                    _ => return Err(ParseColorError {
+                        pos: pos, kind: ParseColorErrorKind::UnknownColorSpace
+                    }),
+                }
+            } else {
+                String::new()
+            };
+        let (args, legacy) = self.arguments(start)?;
+        if legacy && !legacy_allowed {
+            return Err(ParseColorError { pos: start, kind: ParseColorErrorKind::InvalidArguments });
+        }
+        let alpha =
+            match args.get(3) {
+                Some(a) => a.alpha()?,
+                None => 1.,
+            };
+        let (x, y, z) = (args[0], args[1], args[2]);
+        let srgb =
+            match name {
+                "rgb" | "rgba" => Srgb::new(x.channel()?, y.channel()?, z.channel()?),
+                "hsl" | "hsla" => {
+                    encoded(Hsl::new(x.hue()?, y.percentage()?, z.percentage()?).to_rgb())
+                }
+                "hwb" => encoded(Hwb::new(x.hue()?, y.percentage()?, z.percentage()?).to_rgb()),
+                "lab" => {
+                    let lab = Lab::new(x.number(100.)?, y.number(125.)?, z.number(125.)?);
+                    from_rgb(lab_to_rgb(lab))
+                }
+                "lch" => {
+                    let lch = Lch::new(x.number(100.)?, y.number(150.)?, z.hue()?);
+                    from_rgb(lab_to_rgb(lch.to_lab()))
+                }
+                "oklab" => from_rgb(Oklab::new(x.number(1.)?, y.number(0.4)?, z.number(0.4)?).to_rgb()),
+                "oklch" => from_rgb(Oklch::new(x.number(1.)?, y.number(0.4)?, z.hue()?).to_rgb()),
+                _ => {
+                    let v = vec3(x.number(1.)?, y.number(1.)?, z.number(1.)?);
+                    match &*space {
+                        "srgb" => Srgb::new(v.x, v.y, v.z),
+                        "srgb-linear" => from_rgb(Rgb::new(v.x, v.y, v.z)),
+                        "display-p3" => from_rgb(DisplayP3::new(v.x, v.y, v.z).to_rgb()),
+                        "a98-rgb" => from_rgb(AdobeRgb::new(v.x, v.y, v.z).to_rgb()),
+                        "prophoto-rgb" => from_rgb(ProPhotoRgb::new(v.x, v.y, v.z).to_rgb()),
+                        _ => from_rgb(Rec2020::new(v.x, v.y, v.z).to_rgb()),
+                    }
+                }
+            };
+        Ok(Alpha::new(srgb, alpha))
+    }
+
+    // Parses the arguments of a function, up to the closing parenthesis.
+    //
+    // Returns the arguments and whether they are in the legacy syntax.
+    fn arguments(&mut self, start: usize) -> Result<(Vec<Arg>, bool), ParseColorError> {
+        let mut args: Vec<Arg> = Vec::new();
+        let mut legacy: Option<bool> = None;
+        let mut slash: Option<usize> = None;
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                None => return Err(self.error(ParseColorErrorKind::UnexpectedEnd)),
+                Some(b')') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(c) if !args.is_empty() => {
+                    let comma = c == b',';
+                    if comma || c == b'/' {
+                        if legacy == Some(!comma) || slash.is_some() {
+                            return Err(self.error(ParseColorErrorKind::InvalidArguments));
+                        }
+                        if comma {
+                            legacy = Some(true);
+                        } else {
+                            slash = Some(args.len());
+                        }
+                        self.pos += 1;
+                        self.skip_spaces();
+                    } else if legacy == Some(true) {
+                        return Err(self.error(ParseColorErrorKind::InvalidArguments));
+                    } else {
+                        legacy = Some(false);
+                    }
+                }
+                Some(_) => {}
+            }
+            let arg = self.argument()?;
+            args.push(arg);
+        }
+        let valid =
+            match slash {
+                Some(i) => i == 3 && args.len() == 4,
+                None if legacy == Some(true) => args.len() == 3 || args.len() == 4,
+                None => args.len() == 3,
+            };
+        if valid {
+            Ok((args, legacy == Some(true)))
+        } else {
+            Err(ParseColorError { pos: start, kind: ParseColorErrorKind::InvalidArguments })
+        }
+    }
+
+    fn argument(&mut self) -> Result<Arg, ParseColorError> {
+        let start = self.pos;
+        let is_number_char = |c: u8| c.is_ascii_digit() || c == b'.';
+        match self.peek() {
+            None => return Err(self.error(ParseColorErrorKind::UnexpectedEnd)),
+            Some(c) if is_number_char(c) || c == b'+' || c == b'-' => {}
+            Some(c) if (c as char).is_alphabetic() => {
+                // `none` is a missing component, which is `0`.
+                let word = self.skip_while(|c| (c as char).is_alphabetic());
+                if String::from_utf8_lossy(word).eq_ignore_ascii_case("none") {
+                    return Ok(Arg { value: 0., unit: Unit::Number, pos: start });
+                }
+                return Err(ParseColorError {
+                    pos: start, kind: ParseColorErrorKind::InvalidNumber
+                });
+            }
+            Some(_) => return Err(self.error(ParseColorErrorKind::UnexpectedCharacter)),
+        }
+        if self.peek() == Some(b'+') || self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        self.skip_while(is_number_char);
+        if self.peek() == Some(b'e') || self.peek() == Some(b'E') {
+            let e = self.pos;
+            self.pos += 1;
+            if self.peek() == Some(b'+') || self.peek() == Some(b'-') {
+                self.pos += 1;
+            }
+            if self.skip_while(|c| c.is_ascii_digit()).is_empty() {
+                // not an exponent, but a unit.
+                self.pos = e;
+            }
+        }
+        let text = String::from_utf8_lossy(&self.src[start..self.pos]);
+        let value = text.parse::<f32>().map_err(|_| ParseColorError {
+            pos: start, kind: ParseColorErrorKind::InvalidNumber
+        })?;
+        let unit_pos = self.pos;
+        let unit =
+            if self.peek() == Some(b'%') {
+                self.pos += 1;
+                Unit::Percent
+            } else {
+                let u = self.skip_while(|c| (c as char).is_alphabetic());
+                match &*String::from_utf8_lossy(u).to_ascii_lowercase() {
+                    "" => Unit::Number,
+                    "deg" => Unit::Deg,
+                    "rad" => Unit::Rad,
+                    "grad" => Unit::Grad,
+                    "turn" => Unit::Turn,
+                    _ => return Err(ParseColorError {
+                        pos: unit_pos, kind: ParseColorErrorKind::InvalidUnit
+                    }),
+                }
+            };
+        Ok(Arg { value: value, unit: unit, pos: start })
+    }
+}
+
+// Finds the color of keyword `name`, which is in lower case.
+fn keyword(name: &str) -> Option<Alpha<Srgb>> {
+    if name == "transparent" {
+        return Some(Alpha::new(Srgb::new(0., 0., 0.), 0.));
+    }
+    names::iter().find(|&&(n, _)| n == name).map(|&(_, c)| {
+        Alpha::opaque(encoded(c))
+    })
+}
+
+// Re-interprets the channels of `rgb` as sRGB values, as CSS computes HSL
+// and HWB colors from sRGB values.
+#[inline]
+fn encoded(rgb: Rgb) -> Srgb {
+    Srgb::new(rgb.red(), rgb.green(), rgb.blue())
+}
+
+// Converts `lab`, relative to D50 as CSS does, to linear RGB.
+#[inline]
+fn lab_to_rgb(lab: Lab) -> Rgb {
+    lab.to_xyz(D50).adapt(D50, D65, Cat::Bradford).to_rgb()
+}
+
+// Converts `rgb` to CIE L*a*b*, relative to D50 as CSS does.
+#[inline]
+fn rgb_to_lab(rgb: Rgb) -> Lab {
+    let xyz: Xyz = from_rgb(rgb);
+    Lab::from_xyz(xyz.adapt(D65, D50, Cat::Bradford), D50)
+}
+
+// Parses `s` to a sRGB color with alpha.
+fn parse(s: &str) -> Result<Alpha<Srgb>, ParseColorError> {
+    let mut parser = Parser { src: s.as_bytes(), pos: 0 };
+    parser.color()
+}
+
+impl<C: ColorSpace + Copy> FromStr for Alpha<C> {
+    type Err = ParseColorError;
+    #[inline]
+    fn from_str(s: &str) -> Result<Alpha<C>, ParseColorError> {
+        parse(s).map(|clr| clr.convert())
+    }
+}
+
+impl FromStr for Srgb {
+    type Err = ParseColorError;
+    #[inline]
+    fn from_str(s: &str) -> Result<Srgb, ParseColorError> {
+        parse(s).map(|clr| clr.color())
+    }
+}
+
+macro_rules! impl_from_str(
+    ($($t: ty),+) => {
+        $(
+            impl FromStr for $t {
+                type Err = ParseColorError;
+                #[inline]
+                fn from_str(s: &str) -> Result<$t, ParseColorError> {
+                    parse(s).map(|clr| from_rgb(clr.color().to_rgb()))
+                }
+            }
+        )+
+    }
+);
+
+impl_from_str! {
+    Rgb, Hsv, Hsl, Hwb, Hsi, YCbCr, Xyz, Xyy, Lab, Lch, Oklab, Oklch,
+    DisplayP3, AdobeRgb, Rec2020, ProPhotoRgb, AcesCg, Aces2065
+}
+
+/// Notations of CSS colors.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Notation {
+    /// `#rrggbb`, or `#rrggbbaa` if the color is not opaque.
+    Hex,
+    /// `rgb()`, with channels in _[0, 255]_.
+    Rgb,
+    /// `hsl()`, computed from the sRGB values as CSS does, so the hue is
+    /// not `Hsl`'s.
+    Hsl,
+    /// `hwb()`, computed from the sRGB values. It has no legacy syntax.
+    Hwb,
+    /// `lab()`, relative to D50.
+    Lab,
+    /// `lch()`, relative to D50.
+    Lch,
+    /// `oklab()`.
+    Oklab,
+    /// `oklch()`.
+    Oklch,
+    /// `color(display-p3 r g b)`.
+    DisplayP3,
+}
+
+/// Options of writing colors in CSS.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+/// use glm_color::css::{ Format, Notation };
+///
+/// let orange = "#ff8800".parse::<Rgba>().unwrap().with_alpha(0.5);
+/// let fmt = Format::new(Notation::Rgb);
+/// assert_eq!(orange.to_css(&fmt), "rgb(255 136 0 / 0.5)");
+/// assert_eq!(orange.to_css(&fmt.with_legacy(true)), "rgba(255, 136, 0, 0.5)");
+/// assert_eq!(orange.to_css(&Format::new(Notation::Hsl).with_precision(1)),
+///            "hsl(32 100% 50% / 0.5)");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Format {
+    notation: Notation,
+    precision: usize,
+    legacy: bool
+}
+
+impl Format {
+    /// Constructs a `Format` that writes colors in `notation`, in the modern
+    /// syntax, and with at most 3 decimal places.
+    #[inline]
+    pub fn new(notation: Notation) -> Format {
+        Format { notation: notation, precision: 3, legacy: false }
+    }
+
+    /// Returns the notation of _self_.
+    #[inline]
+    pub fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    /// Returns the maximum number of decimal places of numbers.
+    #[inline]
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Returns `true` if _self_ writes `rgb()` and `hsl()` in the legacy
+    /// comma separated syntax.
+    #[inline]
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+
+    /// Returns a new `Format` with given notation `n`, and other options
+    /// from _self_.
+    #[inline]
+    pub fn with_notation(&self, n: Notation) -> Format {
+        Format { notation: n, precision: self.precision, legacy: self.legacy }
+    }
+
+    /// Returns a new `Format` with given maximum number of decimal places
+    /// `p`, and other options from _self_.
+    ///
+    /// Trailing zeros are never written.
+    #[inline]
+    pub fn with_precision(&self, p: usize) -> Format {
+        Format { notation: self.notation, precision: p, legacy: self.legacy }
+    }
+
+    /// Returns a new `Format` that writes `rgb()` and `hsl()` in the legacy
+    /// syntax if `legacy` is `true`, and other options from _self_.
+    ///
+    /// The legacy syntax uses `rgba()` and `hsla()` for colors that are not
+    /// opaque. Other notations do not have the legacy syntax.
+    #[inline]
+    pub fn with_legacy(&self, legacy: bool) -> Format {
+        Format { notation: self.notation, precision: self.precision, legacy: legacy }
+    }
+}
+
+// Writes `v` with at most `precision` decimal places.
+fn number(v: f32, precision: usize) -> String {
+    let s = format!("{:.*}", precision, v);
+    let s =
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &*s
+        };
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+// Writes `rgb` with `alpha` in `format`.
+fn write(rgb: Rgb, alpha: f32, format: &Format) -> String {
+    let num = |v: f32| -> String { number(v, format.precision) };
+    let pct = |v: f32| -> String { num(v * 100.) + "%" };
+    let srgb: Srgb = from_rgb(rgb);
+    let (name, args, legacy_allowed) =
+        match format.notation {
+            Notation::Hex => {
+                let byte = |v: f32| -> u8 { round(clamp(v, 0., 1.) * 255.) as u8 };
+                let v = srgb.as_vec3();
+                let mut s = format!("#{:02x}{:02x}{:02x}", byte(v.x), byte(v.y), byte(v.z));
+                if alpha < 1. {
+                    s.push_str(&format!("{:02x}", byte(alpha)));
+                }
+                return s;
+            }
+            Notation::Rgb => {
+                let v = *srgb.as_vec3() * 255.;
+                ("rgb", [num(v.x), num(v.y), num(v.z)], true)
+            }
+            Notation::Hsl => {
+                let hsl: Hsl = from_rgb(Rgb::new(srgb.red(), srgb.green(), srgb.blue()));
+                ("hsl", [num(degrees(hsl.hue())), pct(hsl.saturation()), pct(hsl.lightness())], true)
+            }
+            Notation::Hwb => {
+                let hwb: Hwb = from_rgb(Rgb::new(srgb.red(), srgb.green(), srgb.blue()));
+                ("hwb", [num(degrees(hwb.hue())), pct(hwb.whiteness()), pct(hwb.blackness())], false)
+            }
+            Notation::Lab => {
+                let lab = rgb_to_lab(rgb);
+                ("lab", [num(lab.lightness()), num(lab.a()), num(lab.b())], false)
+            }
+            Notation::Lch => {
+                let lch = Lch::from_lab(rgb_to_lab(rgb));
+                ("lch", [num(lch.lightness()), num(lch.chroma()), num(degrees(lch.hue()))], false)
+            }
+            Notation::Oklab => {
+                let lab: Oklab = from_rgb(rgb);
+                ("oklab", [num(lab.lightness()), num(lab.a()), num(lab.b())], false)
+            }
+            Notation::Oklch => {
+                let lch: Oklch = from_rgb(rgb);
+                ("oklch", [num(lch.lightness()), num(lch.chroma()), num(degrees(lch.hue()))], false)
+            }
+            Notation::DisplayP3 => {
+                let p3: DisplayP3 = from_rgb(rgb);
+                ("color(display-p3 ", [num(p3.red()), num(p3.green()), num(p3.blue())], false)
+            }
+        };
+    let opaque = alpha >= 1.;
+    if format.legacy && legacy_allowed {
+        if opaque {
+            format!("{}({})", name, args.join(", "))
+        } else {
+            format!("{}a({}, {})", name, args.join(", "), num(alpha))
+        }
+    } else {
+        let open = if name.ends_with(' ') { "" } else { "(" };
+        if opaque {
+            format!("{}{}{})", name, open, args.join(" "))
+        } else {
+            format!("{}{}{} / {})", name, open, args.join(" "), num(alpha))
+        }
+    }
+}
+
+impl<C: ColorSpace + Copy> Alpha<C> {
+    /// Writes _self_ in CSS, using `format`.
+    ///
+    /// The alpha value is written only if _self_ is not opaque.
+    #[inline]
+    pub fn to_css(&self, format: &Format) -> String {
+        write(self.color().to_rgb(), self.alpha(), format)
+    }
+}
+
+macro_rules! impl_to_css(
+    ($($t: ty),+) => {
+        $(
+            impl $t {
+                /// Writes _self_ in CSS, using `format`.
+                ///
+                /// # Example
+                ///
+                /// ```rust
+                /// use glm_color::*;
+                /// use glm_color::css::{ Format, Notation };
+                ///
+                /// let fmt = Format::new(Notation::Hex);
+                /// assert_eq!(RED.to_css(&fmt), "#ff0000");
+                /// ```
+                #[inline]
+                pub fn to_css(&self, format: &Format) -> String {
+                    write(self.to_rgb(), 1., format)
+                }
+            }
+        )+
+    }
+);
+
+impl_to_css! {
+    Rgb, Hsv, Hsl, Hwb, Hsi, YCbCr, Srgb, Xyz, Xyy, Lab, Lch, Oklab, Oklch,
+    DisplayP3, AdobeRgb, Rec2020, ProPhotoRgb, AcesCg, Aces2065
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use rgb::consts::*;
+    use srgb::Srgb;
+    use hsl::Hsl;
+    use hwb::Hwb;
+    use alpha::Rgba;
+    use space::{ ColorSpace, from_rgb };
+    use names;
+    use quickcheck::*;
+    use super::{ ParseColorError, ParseColorErrorKind, Format, Notation };
+    use super::ParseColorErrorKind::*;
+
+    fn srgba(r: u8, g: u8, b: u8, a: f32) -> Rgba {
+        let s = Srgb::new(r as f32 / 255., g as f32 / 255., b as f32 / 255.);
+        Rgba::new(s.to_rgb(), a)
+    }
+
+    fn err(s: &str) -> (usize, ParseColorErrorKind) {
+        let e: ParseColorError = s.parse::<Rgba>().unwrap_err();
+        (e.position(), e.kind())
+    }
+
+    #[test]
+    fn test_hex() {
+        let orange = srgba(255, 136, 0, 1.);
+        let c = |s: &str| -> Rgba { s.parse().unwrap() };
+        assert_eq!(c("#ff8800"), orange);
+        assert_eq!(c("#F80"), orange);
+        assert_eq!(c(" #ff8800ff "), orange);
+        assert!(c("#f80c").is_close_to(&srgba(255, 136, 0, 0.8), 0.000001));
+        assert!(c("#ff880080").is_close_to(&srgba(255, 136, 0, 128. / 255.), 0.000001));
+        assert_eq!(err("#ff888"), (0, InvalidHex));
+        assert_eq!(err("#ff8g00"), (4, InvalidHex));
+        assert_eq!(err("#"), (0, InvalidHex));
+    }
+
+    #[test]
+    fn test_functions() {
+        let orange = srgba(255, 136, 0, 1.);
+        let c = |s: &str| -> Rgba { s.parse().unwrap() };
+        assert_eq!(c("rgb(255, 136, 0)"), orange);
+        assert_eq!(c("RGB(255 136 0)"), orange);
+        assert_eq!(c("rgba(255, 136, 0, 0.5)"), orange.with_alpha(0.5));
+        assert_eq!(c("rgb(255 136 0 / 50%)"), orange.with_alpha(0.5));
+        assert_eq!(c("rgb(100% 0% 0%)"), Rgba::opaque(RED));
+        assert_eq!(c("rgb(300 -1 none)"), Rgba::opaque(RED));
+        assert_eq!(c("hsl(120, 100%, 50%)"), Rgba::opaque(GREEN));
+        assert_eq!(c("hsla(240deg 100% 50% / .25)"), Rgba::new(BLUE, 0.25));
+        assert!(c("hsl(0.5turn 100% 50%)").is_close_to(&Rgba::opaque(CYAN), 0.000001));
+        assert!(c("hsl(-120 100% 50%)").is_close_to(&Rgba::opaque(BLUE), 0.000001));
+        assert!(c("hsl(3.14159265rad 100% 50%)").is_close_to(&Rgba::opaque(CYAN), 0.00001));
+        assert_eq!(c("hwb(0 0% 0%)"), Rgba::opaque(RED));
+        assert_eq!(c("hwb(0 100% 100%)"), Rgba::opaque(Srgb::new(0.5, 0.5, 0.5).to_rgb()));
+        assert_eq!(c("rgb(1e2% 0 0)"), Rgba::opaque(RED));
+        assert!(c("color(display-p3 0.9175 0.2003 0.1386)").is_close_to(&Rgba::opaque(RED), 0.0001));
+        assert!(c("color(a98-rgb 1 1 1)").is_close_to(&Rgba::opaque(WHITE), 0.0001));
+        assert!(c("color(prophoto-rgb 100% 100% 100%)").is_close_to(&Rgba::opaque(WHITE), 0.0001));
+        assert!(c("color(rec2020 0.5675 0.9593 0.269)").is_close_to(&Rgba::opaque(GREEN), 0.001));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let c = |s: &str| -> Rgba { s.parse().unwrap() };
+        assert_eq!(c("red"), Rgba::opaque(RED));
+        assert_eq!(c("CornflowerBlue"), c("#6495ed"));
+        assert_eq!(c("rebeccapurple"), c("#663399"));
+        assert!(names::iter().all(|&(n, c)| {
+            // the sRGB values of a keyword are the constant's channels.
+            let s: Srgb = n.parse().unwrap();
+            let rgb = Rgb::new(s.red(), s.green(), s.blue());
+            // aliases, like `"grey"`, have the name of the same color.
+            rgb == c && names::find(rgb.nearest_name()) == Some(c)
+        }));
+        assert_eq!(c("transparent"), Rgba::new(BLACK, 0.));
+        assert_eq!(err("notacolor"), (0, UnknownKeyword));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(err(""), (0, Empty));
+        assert_eq!(err("   "), (3, Empty));
+        assert_eq!(err("rgb(255, 0 0)"), (11, InvalidArguments));
+        assert_eq!(err("rgb(255 0, 0)"), (9, InvalidArguments));
+        assert_eq!(err("rgb(255 0 0 0)"), (0, InvalidArguments));
+        assert_eq!(err("rgb(255, 0)"), (0, InvalidArguments));
+        assert_eq!(err("rgb(255 0 0 / 1 / 1)"), (16, InvalidArguments));
+        assert_eq!(err("hwb(0, 0%, 0%)"), (0, InvalidArguments));
+        assert_eq!(err("rgb(255 0 0"), (11, UnexpectedEnd));
+        assert_eq!(err("rgb(255 0deg 0)"), (8, InvalidUnit));
+        assert_eq!(err("hsl(0 1px 0)"), (7, InvalidUnit));
+        assert_eq!(err("rgb(1..2 0 0)"), (4, InvalidNumber));
+        assert_eq!(err("rgb(foo 0 0)"), (4, InvalidNumber));
+        assert_eq!(err("foo(0 0 0)"), (0, UnknownFunction));
+        assert_eq!(err("color(xyz-d50 0 0 0)"), (6, UnknownColorSpace));
+        assert_eq!(err("lab(50, 0, 0)"), (0, InvalidArguments));
+        assert_eq!(err("red blue"), (4, UnexpectedCharacter));
+        assert_eq!(err("rgb(255 0 0) x"), (13, UnexpectedCharacter));
+        assert_eq!(format!("{}", "#12".parse::<Rgb>().unwrap_err()),
+                   "invalid hexadecimal color at position 0");
+    }
+
+    #[test]
+    fn test_types() {
+        let rgb: Rgb = "rgb(255 136 0 / 50%)".parse().unwrap();
+        let srgb: Srgb = "#ff8800".parse().unwrap();
+        assert_eq!(srgb, Srgb::new(1., 136. / 255., 0.));
+        assert_eq!(rgb, srgb.to_rgb());
+    }
+
+    #[test]
+    fn test_hue_types() {
+        // hue types are from linear RGB, and CSS from sRGB.
+        let hsl: Hsl = "hsl(30deg 100% 50%)".parse().unwrap();
+        assert!((degrees(hsl.hue()) - 12.8425).abs() < 0.001);
+        assert_eq!(hsl.saturation(), 1.);
+        let hwb: Hwb = "hwb(30deg 0% 0%)".parse().unwrap();
+        assert!((hwb.hue() - hsl.hue()).abs() < 0.00001);
+        let srgb: Srgb = "hsl(30deg 100% 50%)".parse().unwrap();
+        let encoded: Hsl = from_rgb(Rgb::new(srgb.red(), srgb.green(), srgb.blue()));
+        assert!((degrees(encoded.hue()) - 30.).abs() < 0.001);
+        let fmt = Format::new(Notation::Hsl).with_precision(2);
+        assert_eq!(Hsl::new(radians(30.), 1., 0.5).to_css(&fmt), "hsl(44.12 100% 50%)");
+        assert_eq!(hsl.to_css(&fmt), "hsl(30 100% 50%)");
+    }
+
+    #[test]
+    fn test_to_css() {
+        let orange = srgba(255, 136, 0, 1.);
+        let css = |c: Rgba, n: Notation| -> String { c.to_css(&Format::new(n).with_precision(2)) };
+        assert_eq!(css(orange, Notation::Hex), "#ff8800");
+        assert_eq!(css(orange.with_alpha(0.8), Notation::Hex), "#ff8800cc");
+        assert_eq!(css(orange, Notation::Rgb), "rgb(255 136 0)");
+        assert_eq!(css(Rgba::opaque(RED), Notation::Hsl), "hsl(0 100% 50%)");
+        assert_eq!(css(Rgba::new(BLUE, 0.25), Notation::Hwb), "hwb(240 0% 0% / 0.25)");
+        assert_eq!(css(Rgba::opaque(RED), Notation::Lab), "lab(54.29 80.8 69.89)");
+        assert_eq!(css(Rgba::opaque(RED), Notation::Lch), "lch(54.29 106.84 40.86)");
+        assert_eq!(css(Rgba::opaque(RED), Notation::Oklab), "oklab(0.63 0.22 0.13)");
+        assert_eq!(css(Rgba::opaque(BLACK), Notation::Oklch), "oklch(0 0 0)");
+        assert_eq!(css(Rgba::opaque(RED), Notation::DisplayP3), "color(display-p3 0.92 0.2 0.14)");
+        let legacy = Format::new(Notation::Hsl).with_legacy(true);
+        assert_eq!(GREEN.to_css(&legacy), "hsl(120, 100%, 50%)");
+        assert_eq!(Rgba::new(GREEN, 0.5).to_css(&legacy), "hsla(120, 100%, 50%, 0.5)");
+        assert_eq!(GREEN.to_css(&legacy.with_notation(Notation::Oklab).with_precision(0)),
+                   "oklab(1 0 0)");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        fn prop(clr: Rgb, a: f32) -> bool {
+            let rgba = Rgba::new(clr, a.fract().abs());
+            let notations = [
+                Notation::Rgb, Notation::Hsl, Notation::Hwb, Notation::Lab,
+                Notation::Lch, Notation::Oklab, Notation::Oklch, Notation::DisplayP3
+            ];
+            notations.iter().all(|&n| {
+                let fmt = Format::new(n).with_precision(6);
+                let parsed: Rgba = rgba.to_css(&fmt).parse().unwrap();
+                let legacy: Rgba = rgba.to_css(&fmt.with_legacy(true)).parse().unwrap();
+                parsed.is_close_to(&rgba, 0.0001) && legacy == parsed
+            })
+        }
+        quickcheck(prop as fn(Rgb, f32) -> bool);
+    }
+}
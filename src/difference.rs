@@ -0,0 +1,421 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+
+//! Color difference metrics.
+//!
+//! A color difference (ΔE) is a single number that tells how different two
+//! colors look. Unlike `ApproxEq`, which compares the channels of colors,
+//! ΔE is computed in CIE L\*a\*b\*, where the distances are roughly
+//! perceptual. A ΔE of about `1` is the just noticeable difference.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::*;
+//! use glm_color::difference::{ DeltaE, delta_e };
+//!
+//! let red: Hsv = from_rgb(RED);
+//! let dark_red = rgb(250, 0, 0);
+//! assert!(delta_e(&red, &dark_red, DeltaE::Ciede2000) < 1.);
+//! assert!(delta_e(&red, &BLUE, DeltaE::Ciede2000) > 50.);
+//! ```
+//!
+//! # See
+//!
+//! - Wikipedia page [Color difference](http://en.wikipedia.org/wiki/Color_difference).
+//! - Gaurav Sharma, Wencheng Wu and Edul N. Dalal, _The CIEDE2000
+//!   Color-Difference Formula: Implementation Notes, Supplementary Test
+//!   Data, and Mathematical Observations_, Color Research & Application,
+//!   2005.
+
+use super::space::{ ColorSpace, from_rgb };
+use super::lab::Lab;
+use std::f64::consts::PI;
+
+/// The color difference formulas.
+///
+/// CIE94 and CMC are not symmetric. The first color is the reference, and
+/// the weights are computed from it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DeltaE {
+    /// CIE 1976, i.e., the Euclidean distance in CIE L\*a\*b\*.
+    Cie76,
+    /// CIE 1994, with the weights for graphic arts.
+    Cie94GraphicArts,
+    /// CIE 1994, with the weights for textiles.
+    Cie94Textiles,
+    /// CIEDE2000.
+    Ciede2000,
+    /// CMC l:c, with the lightness weight `l` and the chroma weight `c`.
+    ///
+    /// `Cmc(2., 1.)` is used for acceptability, and `Cmc(1., 1.)` for
+    /// perceptibility.
+    Cmc(f32, f32),
+}
+
+// The differences are computed in double precision, because CIEDE2000 is
+// discontinuous around some hue angles.
+#[derive(Copy, Clone)]
+struct Lab64 {
+    l: f64,
+    a: f64,
+    b: f64,
+}
+
+impl Lab64 {
+    #[inline]
+    fn new(lab: Lab) -> Lab64 {
+        Lab64 { l: lab.lightness() as f64, a: lab.a() as f64, b: lab.b() as f64 }
+    }
+
+    #[inline]
+    fn chroma(&self) -> f64 {
+        self.a.hypot(self.b)
+    }
+}
+
+// Hue angle in degrees in the range [0, 360).
+#[inline]
+fn hue(a: f64, b: f64) -> f64 {
+    if a == 0. && b == 0. {
+        0.
+    } else {
+        let h = b.atan2(a).to_degrees();
+        if h < 0. { h + 360. } else { h }
+    }
+}
+
+#[inline]
+fn cos_d(deg: f64) -> f64 {
+    (deg * PI / 180.).cos()
+}
+
+#[inline]
+fn sin_d(deg: f64) -> f64 {
+    (deg * PI / 180.).sin()
+}
+
+// ΔL, ΔC and the square of ΔH, where `r` is the reference.
+#[inline]
+fn delta_lch(r: Lab64, s: Lab64) -> (f64, f64, f64) {
+    let dl = r.l - s.l;
+    let dc = r.chroma() - s.chroma();
+    let (da, db) = (r.a - s.a, r.b - s.b);
+    (dl, dc, (da * da + db * db - dc * dc).max(0.))
+}
+
+fn cie76(r: Lab64, s: Lab64) -> f64 {
+    let (dl, da, db) = (r.l - s.l, r.a - s.a, r.b - s.b);
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+fn cie94(r: Lab64, s: Lab64, kl: f64, k1: f64, k2: f64) -> f64 {
+    let (dl, dc, dh2) = delta_lch(r, s);
+    let c = r.chroma();
+    let sc = 1. + k1 * c;
+    let sh = 1. + k2 * c;
+    let (tl, tc) = (dl / kl, dc / sc);
+    (tl * tl + tc * tc + dh2 / (sh * sh)).sqrt()
+}
+
+fn cmc(r: Lab64, s: Lab64, l: f64, c: f64) -> f64 {
+    let (dl, dc, dh2) = delta_lch(r, s);
+    let cr = r.chroma();
+    let h = hue(r.a, r.b);
+    let sl =
+        if r.l < 16. {
+            0.511
+        } else {
+            0.040975 * r.l / (1. + 0.01765 * r.l)
+        };
+    let sc = 0.0638 * cr / (1. + 0.0131 * cr) + 0.638;
+    let c4 = cr * cr * cr * cr;
+    let f = (c4 / (c4 + 1900.)).sqrt();
+    let t =
+        if (164. ..= 345.).contains(&h) {
+            0.56 + (0.2 * cos_d(h + 168.)).abs()
+        } else {
+            0.36 + (0.4 * cos_d(h + 35.)).abs()
+        };
+    let sh = sc * (f * t + 1. - f);
+    let (tl, tc) = (dl / (l * sl), dc / (c * sc));
+    (tl * tl + tc * tc + dh2 / (sh * sh)).sqrt()
+}
+
+fn ciede2000(r: Lab64, s: Lab64) -> f64 {
+    let pow7 = |x: f64| -> f64 { x.powi(7) };
+    let c25 = pow7(25.);
+    let cm = (r.chroma() + s.chroma()) * 0.5;
+    let g = 0.5 * (1. - (pow7(cm) / (pow7(cm) + c25)).sqrt());
+    let (a1, a2) = (r.a * (1. + g), s.a * (1. + g));
+    let (c1, c2) = (a1.hypot(r.b), a2.hypot(s.b));
+    let (h1, h2) = (hue(a1, r.b), hue(a2, s.b));
+    let cc = c1 * c2;
+
+    let dl = s.l - r.l;
+    let dc = c2 - c1;
+    let dh =
+        if cc == 0. {
+            0.
+        } else if h2 - h1 > 180. {
+            h2 - h1 - 360.
+        } else if h2 - h1 < -180. {
+            h2 - h1 + 360.
+        } else {
+            h2 - h1
+        };
+    let dhh = 2. * cc.sqrt() * sin_d(dh * 0.5);
+
+    let lm = (r.l + s.l) * 0.5;
+    let cm = (c1 + c2) * 0.5;
+    let hm =
+        if cc == 0. {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180. {
+            (h1 + h2) * 0.5
+        } else if h1 + h2 < 360. {
+            (h1 + h2 + 360.) * 0.5
+        } else {
+            (h1 + h2 - 360.) * 0.5
+        };
+    let t = 1. - 0.17 * cos_d(hm - 30.) + 0.24 * cos_d(2. * hm) +
+            0.32 * cos_d(3. * hm + 6.) - 0.2 * cos_d(4. * hm - 63.);
+    let dtheta = 30. * (-((hm - 275.) / 25.).powi(2)).exp();
+    let rc = 2. * (pow7(cm) / (pow7(cm) + c25)).sqrt();
+    let lm50 = (lm - 50.) * (lm - 50.);
+    let sl = 1. + 0.015 * lm50 / (20. + lm50).sqrt();
+    let sc = 1. + 0.045 * cm;
+    let sh = 1. + 0.015 * cm * t;
+    let rt = -sin_d(2. * dtheta) * rc;
+    let (tl, tc, th) = (dl / sl, dc / sc, dhh / sh);
+    (tl * tl + tc * tc + th * th + rt * tc * th).sqrt()
+}
+
+impl Lab {
+    /// Returns the color difference between _self_, the reference, and
+    /// `sample`, using formula `metric`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::difference::DeltaE;
+    ///
+    /// let a = lab(50., 2.6772, -79.7751);
+    /// let b = lab(50., 0., -82.7485);
+    /// assert!((a.delta_e(b, DeltaE::Ciede2000) - 2.0425).abs() < 0.0001);
+    /// ```
+    pub fn delta_e(&self, sample: Lab, metric: DeltaE) -> f32 {
+        let (r, s) = (Lab64::new(*self), Lab64::new(sample));
+        let de =
+            match metric {
+                DeltaE::Cie76 => cie76(r, s),
+                DeltaE::Cie94GraphicArts => cie94(r, s, 1., 0.045, 0.015),
+                DeltaE::Cie94Textiles => cie94(r, s, 2., 0.048, 0.014),
+                DeltaE::Ciede2000 => ciede2000(r, s),
+                DeltaE::Cmc(l, c) => cmc(r, s, l as f64, c as f64),
+            };
+        de as f32
+    }
+}
+
+/// Returns the color difference between `reference` and `sample`, which can
+/// be in different color spaces, using formula `metric`.
+///
+/// Both colors are converted to `Lab` through `Rgb`, so colors out of the
+/// RGB gamut are clampped. Use `Lab::delta_e()` for such colors.
+#[inline]
+pub fn delta_e<A, B>(reference: &A, sample: &B, metric: DeltaE) -> f32
+    where A: ColorSpace, B: ColorSpace
+{
+    let r: Lab = from_rgb(reference.to_rgb());
+    let s: Lab = from_rgb(sample.to_rgb());
+    r.delta_e(s, metric)
+}
+
+/// The CIEDE2000 color difference that is just noticeable.
+pub const JUST_NOTICEABLE_DIFFERENCE: f32 = 1.;
+
+/// Compares colors by how they look, instead of by their channel values as
+/// `ApproxEq` does.
+///
+/// The colors can be in different color spaces. The CIEDE2000 formula is
+/// used, and colors out of the RGB gamut are clampped, as in `delta_e()`.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let red: Hsv = from_rgb(RED);
+/// let ybr: YCbCr = from_rgb(rgb(254, 1, 0));
+/// assert!(red.is_perceptually_eq(&ybr));
+/// assert!(!red.is_perceptually_close_to(&rgb(235, 0, 0), 1.));
+/// assert!(red.is_perceptually_close_to(&rgb(235, 0, 0), 5.));
+/// ```
+pub trait PerceptualEq {
+    /// Returns `true` if the color difference between _self_ and `other` is
+    /// not greater than `max_delta_e`.
+    fn is_perceptually_close_to<C: ColorSpace>(&self, other: &C, max_delta_e: f32) -> bool;
+
+    /// Returns `true` if _self_ and `other` are visually indistinguishable,
+    /// i.e., their color difference is not greater than
+    /// `JUST_NOTICEABLE_DIFFERENCE`.
+    #[inline]
+    fn is_perceptually_eq<C: ColorSpace>(&self, other: &C) -> bool {
+        self.is_perceptually_close_to(other, JUST_NOTICEABLE_DIFFERENCE)
+    }
+}
+
+impl<T: ColorSpace> PerceptualEq for T {
+    #[inline]
+    fn is_perceptually_close_to<C: ColorSpace>(&self, other: &C, max_delta_e: f32) -> bool {
+        delta_e(self, other, DeltaE::Ciede2000) <= max_delta_e
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use space::from_rgb;
+    use lab::{ Lab, lab };
+    use hsv::Hsv;
+    use ycbcr::YCbCr;
+    use super::{ DeltaE, PerceptualEq };
+    use super::DeltaE::*;
+    use quickcheck::*;
+
+    // the test data of Sharma et al., `[L1, a1, b1, L2, a2, b2, ΔE00]`.
+    const SHARMA: [[f32; 7]; 34] = [
+        [50.0000, 2.6772, -79.7751, 50.0000, 0.0000, -82.7485, 2.0425],
+        [50.0000, 3.1571, -77.2803, 50.0000, 0.0000, -82.7485, 2.8615],
+        [50.0000, 2.8361, -74.0200, 50.0000, 0.0000, -82.7485, 3.4412],
+        [50.0000, -1.3802, -84.2814, 50.0000, 0.0000, -82.7485, 1.0000],
+        [50.0000, -1.1848, -84.8006, 50.0000, 0.0000, -82.7485, 1.0000],
+        [50.0000, -0.9009, -85.5211, 50.0000, 0.0000, -82.7485, 1.0000],
+        [50.0000, 0.0000, 0.0000, 50.0000, -1.0000, 2.0000, 2.3669],
+        [50.0000, -1.0000, 2.0000, 50.0000, 0.0000, 0.0000, 2.3669],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0009, 7.1792],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0010, 7.1792],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0011, 7.2195],
+        [50.0000, 2.4900, -0.0010, 50.0000, -2.4900, 0.0012, 7.2195],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0009, -2.4900, 4.8045],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0010, -2.4900, 4.8045],
+        [50.0000, -0.0010, 2.4900, 50.0000, 0.0011, -2.4900, 4.7461],
+        [50.0000, 2.5000, 0.0000, 50.0000, 0.0000, -2.5000, 4.3065],
+        [50.0000, 2.5000, 0.0000, 73.0000, 25.0000, -18.0000, 27.1492],
+        [50.0000, 2.5000, 0.0000, 61.0000, -5.0000, 29.0000, 22.8977],
+        [50.0000, 2.5000, 0.0000, 56.0000, -27.0000, -3.0000, 31.9030],
+        [50.0000, 2.5000, 0.0000, 58.0000, 24.0000, 15.0000, 19.4535],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.1736, 0.5854, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.2972, 0.0000, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 1.8634, 0.5757, 1.0000],
+        [50.0000, 2.5000, 0.0000, 50.0000, 3.2592, 0.3350, 1.0000],
+        [60.2574, -34.0099, 36.2677, 60.4626, -34.1751, 39.4387, 1.2644],
+        [63.0109, -31.0961, -5.8663, 62.8187, -29.7946, -4.0864, 1.2630],
+        [61.2901, 3.7196, -5.3901, 61.4292, 2.2480, -4.9620, 1.8731],
+        [35.0831, -44.1164, 3.7933, 35.0232, -40.0716, 1.5901, 1.8645],
+        [22.7233, 20.0904, -46.6940, 23.0331, 14.9730, -42.5619, 2.0373],
+        [36.4612, 47.8580, 18.3852, 36.2715, 50.5065, 21.2231, 1.4146],
+        [90.8027, -2.0831, 1.4410, 91.1528, -1.6435, 0.0447, 1.4441],
+        [90.9257, -0.5406, -0.9208, 88.6381, -0.8985, -0.7239, 1.5381],
+        [6.7747, -0.2908, -2.4247, 5.8714, -0.0985, -2.2286, 0.6377],
+        [2.0776, 0.0795, -1.1350, 0.9033, -0.0636, -0.5514, 0.9082],
+    ];
+
+    const METRICS: [DeltaE; 6] = [
+        Cie76, Cie94GraphicArts, Cie94Textiles, Ciede2000, Cmc(2., 1.), Cmc(1., 1.)
+    ];
+
+    #[test]
+    fn test_ciede2000() {
+        for s in SHARMA.iter() {
+            let a = lab(s[0], s[1], s[2]);
+            let b = lab(s[3], s[4], s[5]);
+            let de = a.delta_e(b, Ciede2000);
+            assert!(is_close_to(&de, &s[6], 0.0001), "{:?} {}", s, de);
+            // symmetric.
+            assert!(is_close_to(&b.delta_e(a, Ciede2000), &de, 0.0001));
+        }
+    }
+
+    #[test]
+    fn test_delta_e() {
+        // values from the `colormath` package.
+        let a = lab(0.9, 16.3, -2.22);
+        let b = lab(0.7, 14.2, -1.80);
+        assert!(is_close_to(&a.delta_e(b, Cie76), &2.151, 0.001));
+        assert!(is_close_to(&a.delta_e(b, Cie94GraphicArts), &1.249, 0.001));
+        assert!(is_close_to(&a.delta_e(b, Ciede2000), &1.523, 0.001));
+        assert!(is_close_to(&a.delta_e(b, Cmc(2., 1.)), &1.443, 0.001));
+    }
+
+    #[test]
+    fn test_lightness() {
+        // only lightness differs.
+        let a = lab(60., 0., 0.);
+        let b = lab(50., 0., 0.);
+        assert!(is_close_to(&a.delta_e(b, Cie76), &10., 0.0001));
+        assert!(is_close_to(&a.delta_e(b, Cie94GraphicArts), &10., 0.0001));
+        assert!(is_close_to(&a.delta_e(b, Cie94Textiles), &5., 0.0001));
+    }
+
+    #[test]
+    fn test_zero() {
+        fn prop(clr: Rgb) -> bool {
+            let l: Lab = from_rgb(clr);
+            METRICS.iter().all(|&m| {
+                l.delta_e(l, m) == 0. && super::delta_e(&clr, &l, m) < 0.01
+            })
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_cie76() {
+        fn prop(x: Rgb, y: Rgb) -> bool {
+            let a: Lab = from_rgb(x);
+            let b: Lab = from_rgb(y);
+            let d = distance(*a.as_vec3(), *b.as_vec3());
+            is_close_to(&a.delta_e(b, Cie76), &d, 0.001) &&
+            METRICS.iter().all(|&m| a.delta_e(b, m) >= 0.)
+        }
+        quickcheck(prop as fn(Rgb, Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_perceptual_eq() {
+        fn prop(clr: Rgb) -> bool {
+            let hsv: Hsv = from_rgb(clr);
+            let ybr: YCbCr = from_rgb(clr);
+            hsv.is_perceptually_eq(&ybr) &&
+            ybr.is_perceptually_close_to(&clr, 0.01)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+        let gray = Rgb::new(0.5, 0.5, 0.5);
+        assert!(gray.is_perceptually_eq(&Rgb::new(0.5, 0.5, 0.505)));
+        assert!(!gray.is_perceptually_eq(&Rgb::new(0.5, 0.5, 0.55)));
+    }
+}
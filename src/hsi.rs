@@ -0,0 +1,328 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use glm::ext::*;
+use super::space::{ ColorSpace, Pod3, from_rgb };
+use super::rgb::Rgb;
+use std::mem;
+use rand::{ Rand, Rng, thread_rng };
+
+/// The HSI (hue, saturation, intensity) color model.
+///
+/// Hue is an angle in radians in the interval _[0, 2π)_, as `Hsv`'s. Unlike
+/// `Rgb::hue()`, which is hexagonal, the hue of HSI is the angle on the
+/// chromaticity plane, as defined by Gonzalez and Woods. So the hues of a
+/// color in `Hsi` and `Hsv` are same only for primary and secondary colors.
+///
+/// Intensity is the average of red, green and blue channels.
+///
+/// # Note
+///
+/// Not every `Hsi` value is in the RGB gamut. Out of gamut colors are
+/// clampped when converted to `Rgb`.
+///
+/// # See
+///
+/// - ["HSI" in Wikipedia](http://en.wikipedia.org/wiki/HSL_and_HSV#Hue_and_chroma).
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hsi {
+    h: f32,
+    s: f32,
+    i: f32
+}
+
+impl Rand for Hsi {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Hsi {
+        // only colors in the RGB gamut.
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+impl Hsi {
+    /// Constructs an `Hsi` value from given `hue`, `saturation` and
+    /// `intensity` values.
+    ///
+    /// Parameter `hue` is clampped to the interval _[0, 2π)_, and
+    /// `saturation` and `intensity` are clampped to interval _[0, 1]_.
+    #[inline]
+    pub fn new(hue: f32, saturation: f32, intensity: f32) -> Hsi {
+        let mut clr = Hsi { h: 0., s: clamp(saturation, 0., 1.), i: clamp(intensity, 0., 1.) };
+        clr.set_hue(hue);
+        clr
+    }
+
+    /// Constructs an `Hsi` value by randomly choosing a color in the RGB
+    /// gamut using the thread local RNG.
+    #[inline]
+    pub fn rand() -> Hsi {
+        let mut rng = thread_rng();
+        rng.gen()
+    }
+
+    /// Returns the hue of _self_.
+    #[inline]
+    pub fn hue(&self) -> f32 {
+        self.h
+    }
+
+    /// Returns the saturation of _self_.
+    #[inline]
+    pub fn saturation(&self) -> f32 {
+        self.s
+    }
+
+    /// Returns the intensity of _self_.
+    #[inline]
+    pub fn intensity(&self) -> f32 {
+        self.i
+    }
+
+    /// Changes _self_'s hue value to `h`.
+    ///
+    /// The parameter `h` is clampped to the range [0, 2π).
+    #[inline]
+    pub fn set_hue(&mut self, h: f32) {
+        let pi2 = tau();
+        let mut hv = clamp(h, 0., pi2);
+        if hv == pi2 {
+            hv = 0.;
+        }
+        self.h = hv
+    }
+
+    /// Returns a new `Hsi` value with given hue value `h`, and saturation and
+    /// intensity values from _self_.
+    #[inline]
+    pub fn with_hue(&self, h: f32) -> Hsi {
+        let mut c = *self;
+        c.set_hue(h);
+        c
+    }
+
+    /// Changes _self_'s saturation value to `s`.
+    ///
+    /// The parameter 's' is clampped to the rnage [0, 1];
+    #[inline]
+    pub fn set_saturation(&mut self, s: f32) {
+        self.s = clamp(s, 0., 1.);
+    }
+
+    /// Returns a new `Hsi` value with given saturation value `s`, and hue and
+    /// intensity values from _self_.
+    #[inline]
+    pub fn with_saturation(&self, s: f32) -> Hsi {
+        let mut c = *self;
+        c.set_saturation(s);
+        c
+    }
+
+    /// Changes _self_'s intensity value to `i`.
+    ///
+    /// The parameter 'i' is clampped to the rnage [0, 1];
+    #[inline]
+    pub fn set_intensity(&mut self, i: f32) {
+        self.i = clamp(i, 0., 1.);
+    }
+
+    /// Returns a new `Hsi` value with given intensity value `i`, and hue and
+    /// saturation values from _self_.
+    #[inline]
+    pub fn with_intensity(&self, i: f32) -> Hsi {
+        let mut c = *self;
+        c.set_intensity(i);
+        c
+    }
+
+    /// Re-interpret the reference of `Hsi` to `Vec3`.
+    #[inline(always)]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let vec: &Vec3 = unsafe { mem::transmute(self) };
+        vec
+    }
+
+    // Mixes `amt` of gray `g` into _self_.
+    //
+    // Both the intensity and the minimum channel move linearly towards `g`,
+    // and the hue does not change.
+    #[inline]
+    fn mix_gray(&self, g: f32, amt: f32) -> Hsi {
+        let t = clamp(amt, 0., 1.);
+        let i = mix(self.i, g, t);
+        let min = mix(self.i * (1. - self.s), g, t);
+        let s =
+            if is_approx_eq(&i, &0.) {
+                0.
+            } else {
+                clamp(1. - min / i, 0., 1.)
+            };
+        Hsi { h: self.h, s: s, i: i }
+    }
+
+    /// Produces a color by mixing `amt` of white with the receiver.
+    ///
+    /// Parameter `amt` is clampped to the interval _[0, 1]_.
+    #[inline]
+    pub fn tint(&self, amt: f32) -> Hsi {
+        self.mix_gray(1., amt)
+    }
+
+    /// Produces a vector of `n` colors that contain more and more white.
+    ///
+    /// If `n` is `0`, returns an empty vector. Otherwise, the receiver is the
+    /// first element of the vector.
+    #[inline]
+    pub fn tints(&self, n: usize) -> Vec<Hsi> {
+        let d = 1. / (n as f32);
+        (0..n).map(|i| self.tint(d * (i as f32))).collect()
+    }
+
+    /// Produces a color by mixing `amt` of black with the receiver.
+    ///
+    /// For `Hsi` color model, adding black only decreases the intensity.
+    #[inline]
+    pub fn shade(&self, amt: f32) -> Hsi {
+        self.mix_gray(0., amt)
+    }
+
+    /// Produces a vector of `n` colors that contain more and more black.
+    #[inline]
+    pub fn shades(&self, n: usize) -> Vec<Hsi> {
+        let d = 1. / (n as f32);
+        (0..n).map(|i| self.shade(d * (i as f32))).collect()
+    }
+
+    /// Produces a color by mixing `amt` of middle gray with the receiver.
+    #[inline]
+    pub fn tone(&self, amt: f32) -> Hsi {
+        self.mix_gray(0.5, amt)
+    }
+
+    /// Produces a vector of `n` colors that contain more and more middle
+    /// gray.
+    #[inline]
+    pub fn tones(&self, n: usize) -> Vec<Hsi> {
+        let d = 1. / (n as f32);
+        (0..n).map(|i| self.tone(d * (i as f32))).collect()
+    }
+}
+
+/// Equivalent to call `Hsi::new(h, s, i)`.
+#[inline]
+pub fn hsi(h: f32, s: f32, i: f32) -> Hsi {
+    Hsi::new(h, s, i)
+}
+
+impl Eq for Hsi {}
+
+impl ApproxEq for Hsi {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Hsi, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+unsafe impl Pod3 for Hsi {}
+
+impl ColorSpace for Hsi {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Hsi {
+        let v = rgb.as_vec3();
+        let (r, g, b) = (v.x, v.y, v.z);
+        let i = v.sum() / 3.;
+        let min = v.min();
+        if min.is_approx_eq(&v.max()) {
+            // gray color, hue is irrevalent.
+            Hsi { h: 0., s: 0., i: i }
+        } else {
+            let h = (f32::root_three() * (g - b)).atan2(2. * r - g - b);
+            let mut clr = Hsi { h: 0., s: clamp(1. - min / i, 0., 1.), i: i };
+            clr.set_hue(if h < 0. { h + f32::tau() } else { h });
+            clr
+        }
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        let Hsi { h, s, i } = *self;
+        let d120 = radians(120.);
+        let d60 = radians(60.);
+        // the sector that contains the hue.
+        let sector = min(floor(h / d120), 2.);
+        let hs = h - sector * d120;
+        let lo = i * (1. - s);
+        let hi = i * (1. + s * cos(hs) / cos(d60 - hs));
+        let mid = 3. * i - lo - hi;
+        match sector {
+            0. => Rgb::new(hi, mid, lo),
+            1. => Rgb::new(lo, hi, mid),
+            2. => Rgb::new(mid, lo, hi),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use super::Hsi;
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let hsi: Hsi = ColorSpace::from_rgb(clr);
+            hsi.intensity() <= 1. &&
+            hsi.to_rgb().is_close_to(&clr, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool)
+    }
+
+    #[test]
+    fn test_to_rgb_gray() {
+        fn prop(v: f32) -> bool {
+            let gray = Rgb::new(v, v, v);
+            let hsi: Hsi = ColorSpace::from_rgb(gray);
+            hsi.saturation() == 0. &&
+            hsi.to_rgb().is_close_to(&gray, 0.000001)
+        }
+        quickcheck(prop as fn(f32) -> bool)
+    }
+
+    #[test]
+    fn test_shade() {
+        fn prop(clr: Rgb) -> bool {
+            let hsi: Hsi = ColorSpace::from_rgb(clr);
+            let shade = hsi.shade(0.5);
+            let half = Rgb::new(clr.red() * 0.5, clr.green() * 0.5, clr.blue() * 0.5);
+            shade.to_rgb().is_close_to(&half, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool)
+    }
+}
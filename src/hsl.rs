@@ -0,0 +1,356 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use glm::ext::*;
+use super::space::{ ColorSpace, Pod3 };
+use super::rgb::Rgb;
+use std::mem;
+use rand::{ Rand, Rng, thread_rng };
+
+/// The HSL color space.
+///
+/// Hue is an angle in radians in the interval _[0, 2π)_, as `Hsv`'s.
+///
+/// The channels are computed from linear RGB values, while CSS `hsl()` is
+/// computed from sRGB values. So parsing `"hsl(30deg 100% 50%)"` does not
+/// give a hue of 30°, and `to_css()` does not write the hue of _self_ in
+/// `hsl()`.
+///
+/// # See
+///
+/// - ["HSL" in Wikipedia](http://en.wikipedia.org/wiki/HSL_and_HSV).
+/// - [CSS Color Module](http://www.w3.org/TR/css3-color/#hsl-color).
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hsl {
+    h: f32,
+    s: f32,
+    l: f32
+}
+
+impl Rand for Hsl {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Hsl {
+        let h = rng.gen::<f32>() * f32::tau();
+        let s = rng.gen();
+        let l = rng.gen();
+        Hsl { h: h, s: s, l: l }
+    }
+}
+
+impl Hsl {
+    /// Constructs an `Hsl` value from given `hue`, `saturation` and
+    /// `lightness` values.
+    ///
+    /// Parameter `hue` is clampped to the interval _[0, 2π)_, and
+    /// `saturation` and `lightness` are clampped to interval _[0, 1]_.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let red = Hsl::new(7., 2., 0.5);
+    /// assert_eq!(red.hue(), 0.);
+    /// assert_eq!(red.saturation(), 1.);
+    /// assert_eq!(red.to_rgb(), RED);
+    /// ```
+    #[inline]
+    pub fn new(hue: f32, saturation: f32, lightness: f32) -> Hsl {
+        let mut clr = Hsl { h: 0., s: clamp(saturation, 0., 1.), l: clamp(lightness, 0., 1.) };
+        clr.set_hue(hue);
+        clr
+    }
+
+    /// Constructs an `Hsl` value by randomly choosing values for each of the
+    /// three HSL channels using the thread local RNG.
+    #[inline]
+    pub fn rand() -> Hsl {
+        let mut rng = thread_rng();
+        rng.gen()
+    }
+
+    /// Returns the hue of _self_.
+    #[inline]
+    pub fn hue(&self) -> f32 {
+        self.h
+    }
+
+    /// Returns the saturation of _self_.
+    #[inline]
+    pub fn saturation(&self) -> f32 {
+        self.s
+    }
+
+    /// Returns the lightness of _self_.
+    #[inline]
+    pub fn lightness(&self) -> f32 {
+        self.l
+    }
+
+    /// Changes _self_'s hue value to `h`.
+    ///
+    /// The parameter `h` is clampped to the range [0, 2π).
+    #[inline]
+    pub fn set_hue(&mut self, h: f32) {
+        let pi2 = tau();
+        let mut hv = clamp(h, 0., pi2);
+        if hv == pi2 {
+            hv = 0.;
+        }
+        self.h = hv
+    }
+
+    /// Returns a new `Hsl` value with given hue value `h`, and saturation and
+    /// lightness values from _self_.
+    #[inline]
+    pub fn with_hue(&self, h: f32) -> Hsl {
+        let mut c = *self;
+        c.set_hue(h);
+        c
+    }
+
+    /// Changes _self_'s saturation value to `s`.
+    ///
+    /// The parameter 's' is clampped to the rnage [0, 1];
+    #[inline]
+    pub fn set_saturation(&mut self, s: f32) {
+        self.s = clamp(s, 0., 1.);
+    }
+
+    /// Returns a new `Hsl` value with given saturation value `s`, and hue and
+    /// lightness values from _self_.
+    #[inline]
+    pub fn with_saturation(&self, s: f32) -> Hsl {
+        let mut c = *self;
+        c.set_saturation(s);
+        c
+    }
+
+    /// Changes _self_'s lightness value to `l`.
+    ///
+    /// The parameter 'l' is clampped to the rnage [0, 1];
+    #[inline]
+    pub fn set_lightness(&mut self, l: f32) {
+        self.l = clamp(l, 0., 1.);
+    }
+
+    /// Returns a new `Hsl` value with given lightness value `l`, and hue and
+    /// saturation values from _self_.
+    #[inline]
+    pub fn with_lightness(&self, l: f32) -> Hsl {
+        let mut c = *self;
+        c.set_lightness(l);
+        c
+    }
+
+    /// Re-interpret the reference of `Hsl` to `Vec3`.
+    #[inline(always)]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let vec: &Vec3 = unsafe { mem::transmute(self) };
+        vec
+    }
+
+    /// Produces a color by adding white to the receiver.
+    ///
+    /// For `Hsl` color model, adding white means increasing the lightness.
+    /// A color with full lightness is white.
+    ///
+    /// Parameter `amt` specifies absolute lightness to be added.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let red = Hsl::new(0., 1., 0.5);
+    /// assert_eq!(red.tint(1.).to_rgb(), WHITE);
+    /// ```
+    #[inline]
+    pub fn tint(&self, amt: f32) -> Hsl {
+        let l = clamp(amt, 0., 1.);
+        self.with_lightness(self.lightness() + l)
+    }
+
+    /// Produces a vector of `n` colors whose lightness increase monotonically
+    /// and evenly.
+    ///
+    /// If `n` is `0`, returns an empty vector. Otherwise, the receiver is the
+    /// first element of the vector.
+    #[inline]
+    pub fn tints(&self, n: usize) -> Vec<Hsl> {
+        if n == 0 {
+            vec!()
+        } else {
+            let l = self.lightness();
+            let d = (1. - l) / (n as f32);
+            (0..n).map(|i| {
+                self.with_lightness(l + d * (i as f32))
+            }).collect()
+        }
+    }
+
+    /// Produces a color by adding black to the receiver.
+    ///
+    /// For `Hsl` color model, adding black means decreasing the lightness.
+    #[inline]
+    pub fn shade(&self, amt: f32) -> Hsl {
+        let l = clamp(amt, 0., 1.);
+        self.with_lightness(self.lightness() - l)
+    }
+
+    /// Produces `n` colors whose lightness decrease monotonically and evenly.
+    #[inline]
+    pub fn shades(&self, n: usize) -> Vec<Hsl> {
+        if n == 0 {
+            vec!()
+        } else {
+            let l = self.lightness();
+            let d = l / (n as f32);
+            (0..n).map(|i| {
+                self.with_lightness(l - d * (i as f32))
+            }).collect()
+        }
+    }
+
+    /// Produces a color by adding gray to the receiver.
+    ///
+    /// For `Hsl` color model, adding gray means decreasing the saturation.
+    #[inline]
+    pub fn tone(&self, amt: f32) -> Hsl {
+        let s = clamp(amt, 0., 1.);
+        self.with_saturation(self.saturation() - s)
+    }
+
+    /// Produces `n` colors whose saturation decrease monotonically and evenly.
+    #[inline]
+    pub fn tones(&self, n: usize) -> Vec<Hsl> {
+        if n == 0 {
+            vec!()
+        } else {
+            let s = self.saturation();
+            let d = s / (n as f32);
+            (0..n).map(|i| {
+                self.with_saturation(s - d * (i as f32))
+            }).collect()
+        }
+    }
+}
+
+/// Equivalent to call `Hsl::new(h, s, l)`.
+#[inline]
+pub fn hsl(h: f32, s: f32, l: f32) -> Hsl {
+    Hsl::new(h, s, l)
+}
+
+impl Eq for Hsl {}
+
+impl ApproxEq for Hsl {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Hsl, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+unsafe impl Pod3 for Hsl {}
+
+impl ColorSpace for Hsl {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Hsl {
+        let v = rgb.as_vec3();
+        let max = v.max();
+        let min = v.min();
+        let l = (max + min) * 0.5;
+        let s =
+            if max.is_approx_eq(&min) {
+                // gray.
+                0.
+            } else {
+                (max - min) / (1. - abs(2. * l - 1.))
+            };
+        Hsl { h: rgb.hue(), s: clamp(s, 0., 1.), l: l }
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        let Hsl { h, s, l } = *self;
+        if is_approx_eq(&s, &0.) {
+            Rgb::new(l, l, l)
+        } else {
+            let c = (1. - abs(2. * l - 1.)) * s;
+            let hv = degrees(h) / 60.;
+            let hi = floor(hv) % 6.;
+            let x = c * (1. - abs(hv % 2. - 1.));
+            let m = l - c * 0.5;
+            match hi {
+                0. => Rgb::new(c + m, x + m, m),
+                1. => Rgb::new(x + m, c + m, m),
+                2. => Rgb::new(m, c + m, x + m),
+                3. => Rgb::new(m, x + m, c + m),
+                4. => Rgb::new(x + m, m, c + m),
+                5. => Rgb::new(c + m, m, x + m),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use super::Hsl;
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let hsl: Hsl = ColorSpace::from_rgb(clr);
+            hsl.to_rgb().is_close_to(&clr, 0.00001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool)
+    }
+
+    #[test]
+    fn test_to_rgb_gray() {
+        fn prop(v: f32) -> bool {
+            let gray = Rgb::new(v, v, v);
+            let hsl: Hsl = ColorSpace::from_rgb(gray);
+            hsl.saturation() == 0. &&
+            hsl.to_rgb().is_close_to(&gray, 0.000001)
+        }
+        quickcheck(prop as fn(f32) -> bool)
+    }
+
+    #[test]
+    fn test_shades() {
+        let clr = Hsl::new(1., 0.5, 0.8);
+        let shades = clr.shades(4);
+        assert_eq!(shades.len(), 4);
+        assert_eq!(shades[0], clr);
+        assert!(shades.windows(2).all(|w| w[1].lightness() < w[0].lightness()));
+    }
+}
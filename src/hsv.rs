@@ -23,19 +23,23 @@
 
 use glm::*;
 use glm::ext::*;
-use super::space::ColorSpace;
+use super::space::{ ColorSpace, Pod3 };
 use super::rgb::Rgb;
 use std::mem;
 use rand::{ Rand, Rng, thread_rng };
 
 /// The HSV color space.
 ///
+/// As `Hsl`, the channels are computed from linear RGB values, so a color
+/// parsed from CSS `hsl()` or `hwb()` does not have the hue in the string.
+///
 /// # See
 ///
 /// - ["HSV" in Wikipedia](http://en.wikipedia.org/wiki/HSL_and_HSV).
 /// - An excellent [tutorial of color theory in worqx.com](http://www.worqx.com/color/index.htm).
 /// - [How to choose colours procedurally](http://devmag.org.za/2012/07/29/how-to-choose-colours-procedurally-algorithms/).
 /// - [Tints and shades](http://en.wikipedia.org/wiki/Tints_and_shades).
+#[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Hsv {
     h: f32,
@@ -426,6 +430,8 @@ impl ApproxEq for Hsv {
     }
 }
 
+unsafe impl Pod3 for Hsv {}
+
 impl ColorSpace for Hsv {
     #[inline]
     fn from_rgb(rgb: Rgb) -> Hsv {
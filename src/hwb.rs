@@ -0,0 +1,339 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use glm::ext::*;
+use super::space::{ ColorSpace, Pod3 };
+use super::rgb::Rgb;
+use super::hsv::Hsv;
+use std::mem;
+use rand::{ Rand, Rng, thread_rng };
+
+/// The HWB (hue, whiteness, blackness) color model.
+///
+/// Hue is an angle in radians in the interval _[0, 2π)_, as `Hsv`'s. The sum
+/// of whiteness and blackness never exceeds `1`.
+///
+/// Because HWB describes a color as a pure hue mixed with white and black,
+/// `tint()`, `shade()` and `tone()` mix the receiver with white, black and
+/// middle gray exactly.
+///
+/// As `Hsl`, the channels are computed from linear RGB values, not from sRGB
+/// values as CSS `hwb()` is, so they are not the channels in CSS strings.
+///
+/// # See
+///
+/// - ["HWB" in Wikipedia](http://en.wikipedia.org/wiki/HWB_color_model).
+/// - [CSS Color Module Level 4](http://www.w3.org/TR/css-color-4/#the-hwb-notation).
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hwb {
+    h: f32,
+    w: f32,
+    b: f32
+}
+
+impl Rand for Hwb {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Hwb {
+        let h = rng.gen::<f32>() * f32::tau();
+        let w = rng.gen::<f32>();
+        let b = rng.gen::<f32>() * (1. - w);
+        Hwb { h: h, w: w, b: b }
+    }
+}
+
+impl Hwb {
+    /// Constructs an `Hwb` value from given `hue`, `whiteness` and
+    /// `blackness` values.
+    ///
+    /// Parameter `hue` is clampped to the interval _[0, 2π)_, and
+    /// `whiteness` and `blackness` are clampped to interval _[0, 1]_. If the
+    /// sum of `whiteness` and `blackness` is greater than `1`, they are
+    /// scaled so that the sum is `1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let gray = Hwb::new(0., 1., 1.);
+    /// assert_eq!(gray.whiteness(), 0.5);
+    /// assert_eq!(gray.blackness(), 0.5);
+    /// ```
+    #[inline]
+    pub fn new(hue: f32, whiteness: f32, blackness: f32) -> Hwb {
+        let mut clr = Hwb { h: 0., w: 0., b: 0. };
+        clr.set_hue(hue);
+        clr.set_whiteness_blackness(whiteness, blackness);
+        clr
+    }
+
+    /// Constructs an `Hwb` value by randomly choosing values for each of the
+    /// three HWB channels using the thread local RNG.
+    #[inline]
+    pub fn rand() -> Hwb {
+        let mut rng = thread_rng();
+        rng.gen()
+    }
+
+    #[inline]
+    fn set_whiteness_blackness(&mut self, w: f32, b: f32) {
+        let wb = clamp_s(vec2(w, b), 0., 1.);
+        let sum = wb.x + wb.y;
+        if sum > 1. {
+            self.w = wb.x / sum;
+            self.b = wb.y / sum;
+        } else {
+            self.w = wb.x;
+            self.b = wb.y;
+        }
+    }
+
+    /// Returns the hue of _self_.
+    #[inline]
+    pub fn hue(&self) -> f32 {
+        self.h
+    }
+
+    /// Returns the whiteness of _self_.
+    #[inline]
+    pub fn whiteness(&self) -> f32 {
+        self.w
+    }
+
+    /// Returns the blackness of _self_.
+    #[inline]
+    pub fn blackness(&self) -> f32 {
+        self.b
+    }
+
+    /// Changes _self_'s hue value to `h`.
+    ///
+    /// The parameter `h` is clampped to the range [0, 2π).
+    #[inline]
+    pub fn set_hue(&mut self, h: f32) {
+        let pi2 = tau();
+        let mut hv = clamp(h, 0., pi2);
+        if hv == pi2 {
+            hv = 0.;
+        }
+        self.h = hv
+    }
+
+    /// Returns a new `Hwb` value with given hue value `h`, and whiteness and
+    /// blackness values from _self_.
+    #[inline]
+    pub fn with_hue(&self, h: f32) -> Hwb {
+        let mut c = *self;
+        c.set_hue(h);
+        c
+    }
+
+    /// Changes _self_'s whiteness value to `w`.
+    ///
+    /// The parameter `w` is clampped to the range [0, 1]. If the sum of `w`
+    /// and the blackness is greater than `1`, both are scaled so that the sum
+    /// is `1`.
+    #[inline]
+    pub fn set_whiteness(&mut self, w: f32) {
+        let b = self.b;
+        self.set_whiteness_blackness(w, b);
+    }
+
+    /// Returns a new `Hwb` value with given whiteness value `w`, and hue and
+    /// blackness values from _self_.
+    #[inline]
+    pub fn with_whiteness(&self, w: f32) -> Hwb {
+        let mut c = *self;
+        c.set_whiteness(w);
+        c
+    }
+
+    /// Changes _self_'s blackness value to `b`.
+    ///
+    /// The parameter `b` is clampped to the range [0, 1]. If the sum of `b`
+    /// and the whiteness is greater than `1`, both are scaled so that the sum
+    /// is `1`.
+    #[inline]
+    pub fn set_blackness(&mut self, b: f32) {
+        let w = self.w;
+        self.set_whiteness_blackness(w, b);
+    }
+
+    /// Returns a new `Hwb` value with given blackness value `b`, and hue and
+    /// whiteness values from _self_.
+    #[inline]
+    pub fn with_blackness(&self, b: f32) -> Hwb {
+        let mut c = *self;
+        c.set_blackness(b);
+        c
+    }
+
+    /// Re-interpret the reference of `Hwb` to `Vec3`.
+    #[inline(always)]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let vec: &Vec3 = unsafe { mem::transmute(self) };
+        vec
+    }
+
+    // Mixes `amt` of gray `g` into _self_.
+    #[inline]
+    fn mix_gray(&self, g: f32, amt: f32) -> Hwb {
+        let t = clamp(amt, 0., 1.);
+        let w = mix(self.w, g, t);
+        let b = mix(self.b, 1. - g, t);
+        Hwb { h: self.h, w: w, b: b }
+    }
+
+    /// Produces a color by mixing `amt` of white with the receiver.
+    ///
+    /// Parameter `amt` is clampped to the interval _[0, 1]_.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let red: Hwb = from_rgb(RED);
+    /// assert_eq!(red.tint(1.).to_rgb(), WHITE);
+    /// ```
+    #[inline]
+    pub fn tint(&self, amt: f32) -> Hwb {
+        self.mix_gray(1., amt)
+    }
+
+    /// Produces a vector of `n` colors that contain more and more white.
+    ///
+    /// If `n` is `0`, returns an empty vector. Otherwise, the receiver is the
+    /// first element of the vector.
+    #[inline]
+    pub fn tints(&self, n: usize) -> Vec<Hwb> {
+        let d = 1. / (n as f32);
+        (0..n).map(|i| self.tint(d * (i as f32))).collect()
+    }
+
+    /// Produces a color by mixing `amt` of black with the receiver.
+    #[inline]
+    pub fn shade(&self, amt: f32) -> Hwb {
+        self.mix_gray(0., amt)
+    }
+
+    /// Produces a vector of `n` colors that contain more and more black.
+    #[inline]
+    pub fn shades(&self, n: usize) -> Vec<Hwb> {
+        let d = 1. / (n as f32);
+        (0..n).map(|i| self.shade(d * (i as f32))).collect()
+    }
+
+    /// Produces a color by mixing `amt` of middle gray with the receiver.
+    #[inline]
+    pub fn tone(&self, amt: f32) -> Hwb {
+        self.mix_gray(0.5, amt)
+    }
+
+    /// Produces a vector of `n` colors that contain more and more middle
+    /// gray.
+    #[inline]
+    pub fn tones(&self, n: usize) -> Vec<Hwb> {
+        let d = 1. / (n as f32);
+        (0..n).map(|i| self.tone(d * (i as f32))).collect()
+    }
+}
+
+/// Equivalent to call `Hwb::new(h, w, b)`.
+#[inline]
+pub fn hwb(h: f32, w: f32, b: f32) -> Hwb {
+    Hwb::new(h, w, b)
+}
+
+impl Eq for Hwb {}
+
+impl ApproxEq for Hwb {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Hwb, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+unsafe impl Pod3 for Hwb {}
+
+impl ColorSpace for Hwb {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Hwb {
+        let v = rgb.as_vec3();
+        Hwb { h: rgb.hue(), w: v.min(), b: 1. - v.max() }
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        let Hwb { h, w, b } = *self;
+        let sum = w + b;
+        if sum >= 1. {
+            let g = w / sum;
+            Rgb::new(g, g, g)
+        } else {
+            // the pure color, scaled and lifted.
+            let pure = *Hsv::from_hue(h).to_rgb().as_vec3();
+            let v = pure * (1. - sum) + w;
+            Rgb::new(v.x, v.y, v.z)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use super::Hwb;
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let hwb: Hwb = ColorSpace::from_rgb(clr);
+            hwb.whiteness() + hwb.blackness() <= 1. &&
+            hwb.to_rgb().is_close_to(&clr, 0.00001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool)
+    }
+
+    #[test]
+    fn test_tint_shade() {
+        fn prop(clr: Rgb, amt: f32) -> bool {
+            let t = clamp(amt.fract().abs(), 0., 1.);
+            let hwb: Hwb = ColorSpace::from_rgb(clr);
+            let white = Rgb::new(1., 1., 1.);
+            let black = Rgb::new(0., 0., 0.);
+            let mixed = |c: Rgb| -> Rgb {
+                let v = mix_s(*clr.as_vec3(), *c.as_vec3(), t);
+                Rgb::new(v.x, v.y, v.z)
+            };
+            hwb.tint(t).to_rgb().is_close_to(&mixed(white), 0.00001) &&
+            hwb.shade(t).to_rgb().is_close_to(&mixed(black), 0.00001)
+        }
+        quickcheck(prop as fn(Rgb, f32) -> bool)
+    }
+}
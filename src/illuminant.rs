@@ -0,0 +1,267 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! CIE standard illuminants and white points.
+//!
+//! The chromaticities are given for both the CIE 1931 2° and the CIE 1964
+//! 10° standard observers. XYZ values are normalized so that `Y` is `1`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::illuminant::{ Illuminant, Observer, daylight };
+//!
+//! let d65 = Illuminant::D65.chromaticity(Observer::Cie1931);
+//! assert_eq!((d65.x, d65.y), (0.31271, 0.32902));
+//!
+//! // D65 is about the daylight of 6504 K.
+//! let xy = daylight(6504.);
+//! assert!((xy.x - d65.x).abs() < 0.0002 && (xy.y - d65.y).abs() < 0.0002);
+//! ```
+//!
+//! # See
+//!
+//! - Wikipedia page [Standard illuminant](http://en.wikipedia.org/wiki/Standard_illuminant),
+//!   from where the chromaticities are got.
+//! - CIE 15:2004, _Colorimetry_.
+
+use glm::*;
+use super::xyz::{ Xyz, Xyy };
+
+/// Chromaticity of D65 used by sRGB, ITU-R BT.709 and CSS, which is the one
+/// of `Illuminant::D65` for the 2° observer rounded to 4 decimal places.
+///
+/// `xyz::D65`, and the conversions between `Rgb` and `Xyz`, are derived
+/// from it.
+pub const D65_XY: Vec2 = Vec2 { x: 0.3127, y: 0.3290 };
+
+/// Chromaticity of D50 used by ICC profiles and CSS, which is the one of
+/// `Illuminant::D50` for the 2° observer rounded to 4 decimal places.
+///
+/// `xyz::D50` is derived from it.
+pub const D50_XY: Vec2 = Vec2 { x: 0.3457, y: 0.3585 };
+
+/// The CIE standard observers.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Observer {
+    /// The CIE 1931 2° standard observer.
+    Cie1931,
+    /// The CIE 1964 10° supplementary standard observer.
+    Cie1964,
+}
+
+/// CIE standard illuminants.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Illuminant {
+    /// Incandescent light, about 2856 K.
+    A,
+    /// Direct sunlight at noon, about 4874 K. Deprecated by CIE.
+    B,
+    /// Average daylight, about 6774 K. Deprecated by CIE.
+    C,
+    /// Horizon light, about 5003 K. The white point of ICC profiles.
+    D50,
+    /// Mid-morning or mid-afternoon daylight, about 5503 K.
+    D55,
+    /// Noon daylight, about 6504 K. The white point of sRGB.
+    D65,
+    /// North sky daylight, about 7504 K.
+    D75,
+    /// About 9305 K.
+    D93,
+    /// The equal energy illuminant.
+    E,
+    /// Daylight fluorescent.
+    F1,
+    /// Cool white fluorescent.
+    F2,
+    /// White fluorescent.
+    F3,
+    /// Warm white fluorescent.
+    F4,
+    /// Daylight fluorescent.
+    F5,
+    /// Lite white fluorescent.
+    F6,
+    /// Broadband daylight fluorescent. The D65 simulator.
+    F7,
+    /// Broadband fluorescent. The D50 simulator.
+    F8,
+    /// Broadband cool white deluxe fluorescent.
+    F9,
+    /// Narrow tri-band fluorescent.
+    F10,
+    /// Narrow tri-band fluorescent.
+    F11,
+    /// Narrow tri-band fluorescent.
+    F12,
+}
+
+impl Illuminant {
+    /// Returns the chromaticity coordinates `(x, y)` of _self_ for standard
+    /// observer `observer`.
+    pub fn chromaticity(&self, observer: Observer) -> Vec2 {
+        let (xy2, xy10) =
+            match *self {
+                Illuminant::A => ((0.44757, 0.40745), (0.45117, 0.40594)),
+                Illuminant::B => ((0.34842, 0.35161), (0.34980, 0.35270)),
+                Illuminant::C => ((0.31006, 0.31616), (0.31039, 0.31905)),
+                Illuminant::D50 => ((0.34567, 0.35850), (0.34773, 0.35952)),
+                Illuminant::D55 => ((0.33242, 0.34743), (0.33411, 0.34877)),
+                Illuminant::D65 => ((0.31271, 0.32902), (0.31382, 0.33100)),
+                Illuminant::D75 => ((0.29902, 0.31485), (0.29968, 0.31740)),
+                Illuminant::D93 => ((0.28315, 0.29711), (0.28327, 0.30043)),
+                Illuminant::E => ((1. / 3., 1. / 3.), (1. / 3., 1. / 3.)),
+                Illuminant::F1 => ((0.31310, 0.33727), (0.31811, 0.33559)),
+                Illuminant::F2 => ((0.37208, 0.37529), (0.37925, 0.36733)),
+                Illuminant::F3 => ((0.40910, 0.39430), (0.41761, 0.38324)),
+                Illuminant::F4 => ((0.44018, 0.40329), (0.44920, 0.39074)),
+                Illuminant::F5 => ((0.31379, 0.34531), (0.31975, 0.34246)),
+                Illuminant::F6 => ((0.37790, 0.38835), (0.38660, 0.37847)),
+                Illuminant::F7 => ((0.31292, 0.32933), (0.31569, 0.32960)),
+                Illuminant::F8 => ((0.34588, 0.35875), (0.34902, 0.35939)),
+                Illuminant::F9 => ((0.37417, 0.37281), (0.37829, 0.37045)),
+                Illuminant::F10 => ((0.34609, 0.35986), (0.35090, 0.35444)),
+                Illuminant::F11 => ((0.38052, 0.37713), (0.38541, 0.37123)),
+                Illuminant::F12 => ((0.43695, 0.40441), (0.44256, 0.39717)),
+            };
+        let (x, y) =
+            match observer {
+                Observer::Cie1931 => xy2,
+                Observer::Cie1964 => xy10,
+            };
+        vec2(x, y)
+    }
+
+    /// Returns CIE XYZ tristimulus values of _self_ for standard observer
+    /// `observer`, normalized so that `Y` is `1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::xyz::D65;
+    /// use glm_color::illuminant::{ Illuminant, Observer };
+    ///
+    /// let d65 = Illuminant::D65.xyz(Observer::Cie1931);
+    /// assert!((d65.x() - D65.x()).abs() < 0.0001);
+    /// ```
+    #[inline]
+    pub fn xyz(&self, observer: Observer) -> Xyz {
+        let xy = self.chromaticity(observer);
+        Xyy::new(xy.x, xy.y, 1.).to_xyz()
+    }
+}
+
+/// Returns the chromaticity coordinates `(x, y)` of the CIE daylight
+/// illuminant of correlated color temperature `cct`, in kelvins, for the
+/// 2° standard observer.
+///
+/// The D-series illuminants are daylights of temperatures that are
+/// multiplied by `1.4388 / 1.4380`, because of a revision of the Planck's
+/// constant. E.g., D65 is the daylight of about 6504 K. Their tabulated
+/// chromaticities are computed from the spectra, and differ from the
+/// results of this function by about `0.0001`.
+///
+/// # Panics
+///
+/// If `cct` is not in the range *[4000, 25000]*.
+pub fn daylight(cct: f32) -> Vec2 {
+    assert!((4000. ..= 25000.).contains(&cct), "temperature out of range.");
+    let t = cct as f64;
+    let (t2, t3) = (t * t, t * t * t);
+    let x =
+        if t <= 7000. {
+            -4.6070e9 / t3 + 2.9678e6 / t2 + 0.09911e3 / t + 0.244063
+        } else {
+            -2.0064e9 / t3 + 1.9018e6 / t2 + 0.24748e3 / t + 0.237040
+        };
+    let y = -3. * x * x + 2.87 * x - 0.275;
+    vec2(x as f32, y as f32)
+}
+
+/// Returns CIE XYZ tristimulus values of the CIE daylight illuminant of
+/// correlated color temperature `cct`, normalized so that `Y` is `1`.
+///
+/// # Panics
+///
+/// If `cct` is not in the range *[4000, 25000]*.
+#[inline]
+pub fn daylight_xyz(cct: f32) -> Xyz {
+    let xy = daylight(cct);
+    Xyy::new(xy.x, xy.y, 1.).to_xyz()
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use super::{ Illuminant, Observer, daylight, daylight_xyz };
+    use super::Illuminant::*;
+    use quickcheck::*;
+
+    #[test]
+    fn test_daylight() {
+        // the nominal temperatures of the D-series illuminants. The tabulated
+        // chromaticities are computed from spectra, and differ slightly from
+        // the polynomial.
+        let nominal = |t: f32| -> f32 { t * 1.4388 / 1.4380 };
+        for &(d, t) in [(D50, 5000.), (D55, 5500.), (D65, 6500.), (D75, 7500.)].iter() {
+            let xy = d.chromaticity(Observer::Cie1931);
+            assert!(daylight(nominal(t)).is_close_to(&xy, 0.0002));
+        }
+        assert!(daylight_xyz(nominal(6500.)).is_close_to(&D65.xyz(Observer::Cie1931), 0.001));
+    }
+
+    #[test]
+    fn test_daylight_locus() {
+        fn prop(t: f32) -> bool {
+            // the locus is continuous and x decreases with temperature.
+            let cct = 4000. + t.fract().abs() * 20999.;
+            let (a, b) = (daylight(cct), daylight(cct + 1.));
+            b.x < a.x && a.is_close_to(&b, 0.0001)
+        }
+        quickcheck(prop as fn(f32) -> bool);
+        assert!(daylight(7000.).is_close_to(&daylight(7000.01), 0.00001));
+    }
+
+    #[test]
+    fn test_xyz() {
+        let all: [Illuminant; 21] = [
+            A, B, C, D50, D55, D65, D75, D93, E,
+            F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12
+        ];
+        for il in all.iter() {
+            for &o in [Observer::Cie1931, Observer::Cie1964].iter() {
+                let xyz = il.xyz(o);
+                assert!(is_close_to(&xyz.y(), &1., 0.00001));
+                assert!(xyz.chromaticity().is_close_to(&il.chromaticity(o), 0.00001));
+            }
+        }
+        assert!(E.xyz(Observer::Cie1964).as_vec3().is_close_to(&vec3(1., 1., 1.), 0.00001));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_daylight_range() {
+        daylight(3000.);
+    }
+}
@@ -0,0 +1,225 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use glm::ext::cbrt;
+use super::space::{ ColorSpace, Pod3, from_rgb };
+use super::rgb::Rgb;
+use super::xyz::{ Xyz, D65 };
+use std::mem;
+use rand::{ Rand, Rng };
+
+// CIE constants ε (216/24389) and κ (24389/27).
+const EPSILON: f32 = 0.008856452;
+const KAPPA: f32 = 903.2963;
+
+/// The CIE L\*a\*b\* color space.
+///
+/// `Lab` values are converted to and from linear RGB through CIE XYZ. The
+/// `ColorSpace` implementation uses the D65 reference white. Use
+/// `from_xyz()` and `to_xyz()` for other reference whites.
+///
+/// # See
+///
+/// - Wikipedia page [Lab color space](http://en.wikipedia.org/wiki/Lab_color_space).
+/// - [Bruce Lindbloom's math page](http://www.brucelindbloom.com/index.html?Math.html),
+///   from where the numbers used in conversion are got.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let white: Lab = from_rgb(WHITE);
+/// assert!((white.lightness() - 100.).abs() < 0.001);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Lab {
+    l: f32,
+    a: f32,
+    b: f32
+}
+
+impl Lab {
+    /// Constructs a `Lab` value.
+    ///
+    /// Parameter `l` is clampped to the range *[0, 100]*. `a` and `b` are not
+    /// bounded.
+    #[inline]
+    pub fn new(l: f32, a: f32, b: f32) -> Lab {
+        Lab { l: clamp(l, 0., 100.), a: a, b: b }
+    }
+
+    /// Returns the lightness (`L*`) of _self_, which is a value in the range
+    /// *[0, 100]*.
+    #[inline]
+    pub fn lightness(&self) -> f32 {
+        self.l
+    }
+
+    /// Returns the `a*` component of _self_, i.e., the position between green
+    /// (negative) and red (positive).
+    #[inline]
+    pub fn a(&self) -> f32 {
+        self.a
+    }
+
+    /// Returns the `b*` component of _self_, i.e., the position between blue
+    /// (negative) and yellow (positive).
+    #[inline]
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    /// Re-interprets a reference of `Lab` to `Vec3`.
+    #[inline]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let v: &Vec3 = unsafe { mem::transmute(self) };
+        v
+    }
+
+    /// Constructs a `Lab` value from `xyz`, relative to reference white
+    /// `white`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate glm;
+    /// # extern crate glm_color;
+    /// # fn main() {
+    /// use glm::*;
+    /// use glm_color::*;
+    ///
+    /// let d50 = xyz(0.96422, 1., 0.82521);
+    /// let red = Lab::from_xyz(from_rgb(RED), d50);
+    /// assert!(is_close_to(&red.to_xyz(d50).to_rgb(), &RED, 0.0001));
+    /// # }
+    /// ```
+    pub fn from_xyz(xyz: Xyz, white: Xyz) -> Lab {
+        let f = (*xyz.as_vec3() / *white.as_vec3()).map(|t| {
+            if t > EPSILON {
+                cbrt(t)
+            } else {
+                (KAPPA * t + 16.) / 116.
+            }
+        });
+        Lab {
+            l: 116. * f.y - 16.,
+            a: 500. * (f.x - f.y),
+            b: 200. * (f.y - f.z)
+        }
+    }
+
+    /// Converts _self_ to CIE XYZ color space, relative to reference white
+    /// `white`.
+    pub fn to_xyz(&self, white: Xyz) -> Xyz {
+        let fy = (self.l + 16.) / 116.;
+        let fx = fy + self.a / 500.;
+        let fz = fy - self.b / 200.;
+        let finv = |f: f32| -> f32 {
+            let f3 = f * f * f;
+            if f3 > EPSILON {
+                f3
+            } else {
+                (116. * f - 16.) / KAPPA
+            }
+        };
+        let yr =
+            if self.l > KAPPA * EPSILON {
+                fy * fy * fy
+            } else {
+                self.l / KAPPA
+            };
+        let v = vec3(finv(fx), yr, finv(fz)) * *white.as_vec3();
+        Xyz::new(v.x, v.y, v.z)
+    }
+}
+
+impl Eq for Lab {}
+
+impl ApproxEq for Lab {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Lab, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+impl Rand for Lab {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Lab {
+        // only colors in the RGB gamut.
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+unsafe impl Pod3 for Lab {}
+
+impl ColorSpace for Lab {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Lab {
+        Lab::from_xyz(from_rgb(rgb), D65)
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        self.to_xyz(D65).to_rgb()
+    }
+}
+
+/// Equivalent to `Lab::new()`.
+#[inline]
+pub fn lab(l: f32, a: f32, b: f32) -> Lab {
+    Lab::new(l, a, b)
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use rgb::consts::{ BLACK, WHITE };
+    use super::Lab;
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let lab: Lab = ColorSpace::from_rgb(clr);
+            let rgb = lab.to_rgb();
+            lab.lightness() >= 0. &&
+            lab.lightness() <= 100.0001 &&
+            is_close_to(&rgb, &clr, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_white_black() {
+        let white: Lab = ColorSpace::from_rgb(WHITE);
+        assert!(is_close_to(&white, &Lab::new(100., 0., 0.), 0.01));
+        let black: Lab = ColorSpace::from_rgb(BLACK);
+        assert!(is_close_to(&black, &Lab::new(0., 0., 0.), 0.0001));
+    }
+}
@@ -0,0 +1,324 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use glm::ext::*;
+use super::space::{ ColorSpace, Pod3, from_rgb };
+use super::rgb::Rgb;
+use super::lab::Lab;
+use std::mem;
+use rand::{ Rand, Rng };
+
+/// The CIE LCh(ab) color space, i.e., the cylindrical representation of
+/// CIE L\*a\*b\*.
+///
+/// Hue is an angle in radians in the interval _[0, 2π)_, as `Hsv`'s. Unlike
+/// `Hsv`, rotating the hue of a `Lch` value keeps its perceived lightness and
+/// chroma, so the harmony methods (e.g., `complement()` and `triad()`)
+/// produce colors that look equally light and vivid.
+///
+/// # Note
+///
+/// Not every `Lch` value is in the RGB gamut. Out of gamut colors are
+/// clampped when converted to `Rgb`.
+///
+/// # See
+///
+/// - Wikipedia page [CIELCh](http://en.wikipedia.org/wiki/Lab_color_space#Cylindrical_representation:_CIELCh_or_CIEHLC).
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Lch {
+    l: f32,
+    c: f32,
+    h: f32
+}
+
+impl Lch {
+    /// Constructs a `Lch` value from given `lightness`, `chroma` and `hue`.
+    ///
+    /// Parameter `lightness` is clampped to the interval _[0, 100]_,
+    /// negative `chroma` is clampped to `0`, and `hue` is clampped to the
+    /// interval _[0, 2π)_.
+    #[inline]
+    pub fn new(lightness: f32, chroma: f32, hue: f32) -> Lch {
+        let mut clr = Lch { l: clamp(lightness, 0., 100.), c: max(chroma, 0.), h: 0. };
+        clr.set_hue(hue);
+        clr
+    }
+
+    /// Converts a `Lab` value to `Lch`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate glm;
+    /// # extern crate glm_color;
+    /// # fn main() {
+    /// use glm::*;
+    /// use glm_color::*;
+    ///
+    /// let lch = Lch::from_lab(lab(50., 0., 20.));
+    /// assert_eq!(lch.chroma(), 20.);
+    /// assert_eq!(lch.hue(), radians(90.));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_lab(lab: Lab) -> Lch {
+        let (a, b) = (lab.a(), lab.b());
+        let c = sqrt(a * a + b * b);
+        let mut clr = Lch { l: lab.lightness(), c: c, h: 0. };
+        // for gray colors, hue is irrevalent.
+        if !is_approx_eq(&c, &0.) {
+            let h = b.atan2(a);
+            clr.set_hue(if h < 0. { h + f32::tau() } else { h });
+        }
+        clr
+    }
+
+    /// Converts _self_ to `Lab`.
+    #[inline]
+    pub fn to_lab(&self) -> Lab {
+        Lab::new(self.l, self.c * cos(self.h), self.c * sin(self.h))
+    }
+
+    /// Returns the lightness of _self_.
+    #[inline]
+    pub fn lightness(&self) -> f32 {
+        self.l
+    }
+
+    /// Returns the chroma of _self_.
+    #[inline]
+    pub fn chroma(&self) -> f32 {
+        self.c
+    }
+
+    /// Returns the hue of _self_.
+    #[inline]
+    pub fn hue(&self) -> f32 {
+        self.h
+    }
+
+    /// Changes _self_'s lightness value to `l`.
+    ///
+    /// The parameter `l` is clampped to the range [0, 100].
+    #[inline]
+    pub fn set_lightness(&mut self, l: f32) {
+        self.l = clamp(l, 0., 100.);
+    }
+
+    /// Returns a new `Lch` value with given lightness value `l`, and chroma
+    /// and hue values from _self_.
+    #[inline]
+    pub fn with_lightness(&self, l: f32) -> Lch {
+        let mut c = *self;
+        c.set_lightness(l);
+        c
+    }
+
+    /// Changes _self_'s chroma value to `c`.
+    ///
+    /// Negative `c` is clampped to `0`.
+    #[inline]
+    pub fn set_chroma(&mut self, c: f32) {
+        self.c = max(c, 0.);
+    }
+
+    /// Returns a new `Lch` value with given chroma value `c`, and lightness
+    /// and hue values from _self_.
+    #[inline]
+    pub fn with_chroma(&self, c: f32) -> Lch {
+        let mut clr = *self;
+        clr.set_chroma(c);
+        clr
+    }
+
+    /// Changes _self_'s hue value to `h`.
+    ///
+    /// The parameter `h` is clampped to the range [0, 2π).
+    #[inline]
+    pub fn set_hue(&mut self, h: f32) {
+        let pi2 = tau();
+        let mut hv = clamp(h, 0., pi2);
+        if hv == pi2 {
+            hv = 0.;
+        }
+        self.h = hv
+    }
+
+    /// Returns a new `Lch` value with given hue value `h`, and lightness and
+    /// chroma values from _self_.
+    #[inline]
+    pub fn with_hue(&self, h: f32) -> Lch {
+        let mut c = *self;
+        c.set_hue(h);
+        c
+    }
+
+    /// Re-interpret the reference of `Lch` to `Vec3`.
+    #[inline(always)]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let vec: &Vec3 = unsafe { mem::transmute(self) };
+        vec
+    }
+
+    /// Returns the complementary color of _self_.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glm_color::*;
+    ///
+    /// let red: Lch = from_rgb(RED);
+    /// let cyan = red.complement();
+    /// assert_eq!(cyan.lightness(), red.lightness());
+    /// assert_eq!(cyan.chroma(), red.chroma());
+    /// ```
+    #[inline]
+    pub fn complement(&self) -> Lch {
+        self.with_hue(fmod(self.hue() + f32::pi(), tau()))
+    }
+
+    /// Returns a pair of colors that are splited from the complementary color
+    /// of _self_,
+    ///
+    /// The 2 colors have same distances to the complementary color on the
+    /// hue circle. As `Hsv::split_complement()`, this distance is fixed to
+    /// `30` degrees.
+    #[inline]
+    pub fn split_complement(&self) -> (Lch, Lch) {
+        let pi2 = tau();
+        let h1 = fmod(self.hue() + radians(150.), pi2);
+        let h2 = fmod(self.hue() + radians(210.), pi2);
+        (self.with_hue(h1), self.with_hue(h2))
+    }
+
+    /// Returns 2 pairs of complementary colors. The first colors of both pairs
+    /// are the result of `split_complement` of _self_.
+    #[inline]
+    pub fn double_complement(&self) -> ((Lch, Lch), (Lch, Lch)) {
+        let (c1, c2) = self.split_complement();
+        ((c1.complement(), c1), (c2.complement(), c2))
+    }
+
+    /// Returns other 2 colors of triad colors that includes _self_.
+    #[inline]
+    pub fn triad(&self) -> (Lch, Lch) {
+        let pi2 = tau();
+        let d120 = radians(120.);
+        let h1 = fmod(self.hue() + d120, pi2);
+        let h2 = fmod(self.hue() + d120 + d120, pi2);
+        (self.with_hue(h1), self.with_hue(h2))
+    }
+
+    /// Returns `n` colors that are analogous to the receiver.
+    ///
+    /// The hues of returned colors spread evenly in the arc of `span` that
+    /// starts from the hue of _self_.
+    #[inline]
+    pub fn analogs(&self, n: usize, span: f32) -> Vec<Lch> {
+        if span == 0. || n == 0 {
+            vec!()
+        } else {
+            let d = span / (n as f32);
+            let h = self.hue();
+            (0..n).map(|i| -> Lch {
+                let hue = fmod(h + d * (i as f32), tau());
+                Lch { l: self.l, c: self.c, h: hue }
+            }).collect()
+        }
+    }
+}
+
+/// Equivalent to call `Lch::new(l, c, h)`.
+#[inline]
+pub fn lch(l: f32, c: f32, h: f32) -> Lch {
+    Lch::new(l, c, h)
+}
+
+impl Eq for Lch {}
+
+impl ApproxEq for Lch {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Lch, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+impl Rand for Lch {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Lch {
+        // only colors in the RGB gamut.
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+unsafe impl Pod3 for Lch {}
+
+impl ColorSpace for Lch {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Lch {
+        Lch::from_lab(from_rgb(rgb))
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        self.to_lab().to_rgb()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use super::Lch;
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let lch: Lch = ColorSpace::from_rgb(clr);
+            let rgb = lch.to_rgb();
+            lch.hue() >= 0. &&
+            lch.hue() < ext::tau() &&
+            is_close_to(&rgb, &clr, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_harmony() {
+        fn prop(clr: Rgb) -> bool {
+            let lch: Lch = ColorSpace::from_rgb(clr);
+            let (c1, c2) = lch.triad();
+            let (s1, s2) = lch.split_complement();
+            [lch.complement(), c1, c2, s1, s2].iter().all(|c| {
+                c.lightness() == lch.lightness() &&
+                c.chroma() == lch.chroma()
+            })
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+}
@@ -25,8 +25,9 @@
 //! extension to the [`glm`](https://crates.io/crates/glm) crate.
 //!
 //! `glm_color` treats color values as numbers, instead of somthing that can be
-//! used in rendering directly. So things like data format, order of channels,
-//! and even alpha channel, are not handled by this library.
+//! used in rendering directly. So things like data format and order of
+//! channels are not handled by this library. Alpha channel can be attached to
+//! a color value of any color space with the `Alpha` wrapper.
 //!
 //! The only interesting part of this crate is functions in `Rgb` and `Hsv`
 //! color spaces that produce colors procedurally. The design of these functions
@@ -99,16 +100,41 @@
 //! let ybr: YCbCr = from_rgb(rgb);
 //! red = to_rgb(&ybr);
 //! let srgb = Srgb::from_rgb(rgb);
+//! let lab: Lab = from_rgb(rgb);
+//!
+//! // Slices of colors are converted in bulk.
+//! let mut pixels = vec![RED; 1024];
+//! let mut ybrs = vec![YCbCr::new(0., 0., 0.); 1024];
+//! convert_slice(&pixels, &mut ybrs);
+//! let srgbs: &mut [Srgb] = convert_in_place(&mut pixels);
+//! # }
+//! ```
+//! ## Packing colors
+//!
+//! ```rust
+//! # extern crate glm;
+//! # extern crate glm_color;
+//! # fn main() {
+//! use glm_color::*;
+//! use glm_color::pack::{ PixelFormat, Encoding };
+//!
+//! let px = rgb(255, 136, 0).pack(PixelFormat::Rgba8888, Encoding::Linear);
+//! assert_eq!(px, 0xFF8800FF);
+//! let orange = Rgba::unpack(px, PixelFormat::Rgba8888, Encoding::Linear);
+//! assert_eq!(orange, Rgba::opaque(rgb(255, 136, 0)));
+//!
+//! // GPU formats, encoded in sRGB.
+//! let px = orange.pack(PixelFormat::Rgb10A2, Encoding::Srgb);
+//! let hdr = orange.pack(PixelFormat::Rgba16F, Encoding::Linear);
 //! # }
 //! ```
-// TODO: examples for packing/unpacking colors.
 
 extern crate rand;
 extern crate glm;
 #[cfg(test)]
 extern crate quickcheck;
 
-pub use space::{ ColorSpace, from_rgb, to_rgb };
+pub use space::{ ColorSpace, from_rgb, to_rgb, convert_slice, convert_in_place };
 
 pub use rgb::{ Rgb, rgb, gray, grey };
 
@@ -116,13 +142,57 @@ pub use rgb::consts::*;
 
 pub use hsv::{ Hsv, hsv };
 
+pub use hsl::{ Hsl, hsl };
+
+pub use hwb::{ Hwb, hwb };
+
+pub use hsi::{ Hsi, hsi };
+
 pub use ycbcr::{ YCbCr, ycbcr };
 
 pub use srgb::{ Srgb, srgb };
 
+pub use xyz::{ Xyz, xyz, Xyy, xyy };
+
+pub use lab::{ Lab, lab };
+
+pub use lch::{ Lch, lch };
+
+pub use oklab::{ Oklab, oklab, Oklch, oklch };
+
+pub use working::{
+    DisplayP3, display_p3, AdobeRgb, adobe_rgb, Rec2020, rec2020,
+    ProPhotoRgb, prophoto_rgb, AcesCg, acescg, Aces2065, aces2065
+};
+
+pub use difference::PerceptualEq;
+
+pub use alpha::{ Alpha, Rgba, Hsva, rgba, hsva };
+
 mod space;
+mod simd;
 pub mod rgb;
 pub mod hsv;
+pub mod hsl;
+pub mod hwb;
+pub mod hsi;
 pub mod ycbcr;
 pub mod srgb;
-// TODO: pub mod Lab;
+pub mod xyz;
+pub mod lab;
+pub mod lch;
+pub mod oklab;
+pub mod working;
+pub mod primaries;
+pub mod adapt;
+pub mod illuminant;
+pub mod temperature;
+pub mod difference;
+pub mod contrast;
+pub mod alpha;
+pub mod composite;
+pub mod blend;
+pub mod css;
+pub mod names;
+pub mod pack;
+pub mod quantize;
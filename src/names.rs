@@ -0,0 +1,326 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Runtime lookup of the named color constants in `rgb::consts`.
+//!
+//! Names are the constant names in lower case without underscores, e.g.,
+//! `"darkslategray"` for `DARK_SLATE_GRAY`, which are also the CSS color
+//! keywords. Aliases, like `"grey"` and `"gray"`, are separate entries.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::*;
+//! use glm_color::names;
+//!
+//! assert_eq!(Rgb::from_name("Dark_Slate-Gray"), Some(DARK_SLATE_GRAY));
+//! assert_eq!(names::iter().count(), 148);
+//! assert_eq!(Rgb::new(0.99, 0.01, 0.).nearest_name(), "red");
+//! ```
+
+use glm::*;
+use super::space::from_rgb;
+use super::rgb::Rgb;
+use super::rgb::consts::*;
+use super::lab::Lab;
+use std::slice;
+use std::sync::OnceLock;
+
+// Sorted by name.
+static NAMES: &[(&str, Rgb)] = &[
+    ("aliceblue", ALICE_BLUE),
+    ("antiquewhite", ANTIQUE_WHITE),
+    ("aqua", AQUA),
+    ("aquamarine", AQUA_MARINE),
+    ("azure", AZURE),
+    ("beige", BEIGE),
+    ("bisque", BISQUE),
+    ("black", BLACK),
+    ("blanchedalmond", BLANCHED_ALMOND),
+    ("blue", BLUE),
+    ("blueviolet", BLUE_VIOLET),
+    ("brown", BROWN),
+    ("burlywood", BURLY_WOOD),
+    ("cadetblue", CADET_BLUE),
+    ("chartreuse", CHARTREUSE),
+    ("chocolate", CHOCOLATE),
+    ("coral", CORAL),
+    ("cornflowerblue", CORNFLOWER_BLUE),
+    ("cornsilk", CORNSILK),
+    ("crimson", CRIMSON),
+    ("cyan", CYAN),
+    ("darkblue", DARK_BLUE),
+    ("darkcyan", DARK_CYAN),
+    ("darkgoldenrod", DARK_GOLDEN_ROD),
+    ("darkgray", DARK_GRAY),
+    ("darkgreen", DARK_GREEN),
+    ("darkgrey", DARK_GREY),
+    ("darkkhaki", DARK_KHAKI),
+    ("darkmagenta", DARK_MAGENTA),
+    ("darkolivegreen", DARK_OLIVEGREEN),
+    ("darkorange", DARK_ORANGE),
+    ("darkorchid", DARK_ORCHID),
+    ("darkred", DARK_RED),
+    ("darksalmon", DARK_SALMON),
+    ("darkseagreen", DARK_SEA_GREEN),
+    ("darkslateblue", DARK_SLATE_BLUE),
+    ("darkslategray", DARK_SLATE_GRAY),
+    ("darkslategrey", DARK_SLATE_GREY),
+    ("darkturquoise", DARK_TURQUOISE),
+    ("darkviolet", DARK_VIOLET),
+    ("deeppink", DEEP_PINK),
+    ("deepskyblue", DEEP_SKY_BLUE),
+    ("dimgray", DIM_GRAY),
+    ("dimgrey", DIM_GREY),
+    ("dodgerblue", DODGER_BLUE),
+    ("firebrick", FIRE_BRICK),
+    ("floralwhite", FLORAL_WHITE),
+    ("forestgreen", FOREST_GREEN),
+    ("fuchsia", FUCHSIA),
+    ("gainsboro", GAINSBORO),
+    ("ghostwhite", GHOST_WHITE),
+    ("gold", GOLD),
+    ("goldenrod", GOLDEN_ROD),
+    ("gray", GRAY),
+    ("green", GREEN),
+    ("greenyellow", GREEN_YELLOW),
+    ("grey", GREY),
+    ("honeydew", HONEYDEW),
+    ("hotpink", HOT_PINK),
+    ("indianred", INDIAN_RED),
+    ("indigo", INDIGO),
+    ("ivory", IVORY),
+    ("khaki", KHAKI),
+    ("lavender", LAVENDER),
+    ("lavenderblush", LAVENDER_BLUSH),
+    ("lawngreen", LAWN_GREEN),
+    ("lemonchiffon", LEMON_CHIFFON),
+    ("lightblue", LIGHT_BLUE),
+    ("lightcoral", LIGHT_CORAL),
+    ("lightcyan", LIGHT_CYAN),
+    ("lightgoldenrodyellow", LIGHT_GOLDEN_ROD_YELLOW),
+    ("lightgray", LIGHT_GRAY),
+    ("lightgreen", LIGHT_GREEN),
+    ("lightgrey", LIGHT_GREY),
+    ("lightpink", LIGHT_PINK),
+    ("lightsalmon", LIGHT_SALMON),
+    ("lightseagreen", LIGHT_SEA_GREEN),
+    ("lightskyblue", LIGHT_SKY_BLUE),
+    ("lightslategray", LIGHT_SLATE_GRAY),
+    ("lightslategrey", LIGHT_SLATE_GREY),
+    ("lightsteelblue", LIGHT_STEEL_BLUE),
+    ("lightyellow", LIGHT_YELLOW),
+    ("lime", LIME),
+    ("limegreen", LIME_GREEN),
+    ("linen", LINEN),
+    ("magenta", MAGENTA),
+    ("maroon", MAROON),
+    ("mediumaquamarine", MEDIUM_AQUA_MARINE),
+    ("mediumblue", MEDIUM_BLUE),
+    ("mediumorchid", MEDIUM_ORCHID),
+    ("mediumpurple", MEDIUM_PURPLE),
+    ("mediumseagreen", MEDIUM_SEA_GREEN),
+    ("mediumslateblue", MEDIUM_SLATE_BLUE),
+    ("mediumspringgreen", MEDIUM_SPRING_GREEN),
+    ("mediumturquoise", MEDIUM_TURQUOISE),
+    ("mediumvioletred", MEDIUM_VIOLET_RED),
+    ("midnightblue", MIDNIGHT_BLUE),
+    ("mintcream", MINT_CREAM),
+    ("mistyrose", MISTY_ROSE),
+    ("moccasin", MOCCASIN),
+    ("navajowhite", NAVAJO_WHITE),
+    ("navy", NAVY),
+    ("oldlace", OLD_LACE),
+    ("olive", OLIVE),
+    ("olivedrab", OLIVE_DRAB),
+    ("orange", ORANGE),
+    ("orangered", ORANGE_RED),
+    ("orchid", ORCHID),
+    ("palegoldenrod", PALE_GOLDEN_ROD),
+    ("palegreen", PALE_GREEN),
+    ("paleturquoise", PALE_TURQUOISE),
+    ("palevioletred", PALE_VIOLET_RED),
+    ("papayawhip", PAPAYA_WHIP),
+    ("peachpuff", PEACH_PUFF),
+    ("peru", PERU),
+    ("pink", PINK),
+    ("plum", PLUM),
+    ("powderblue", POWDER_BLUE),
+    ("purple", PURPLE),
+    ("rebeccapurple", REBECCA_PURPLE),
+    ("red", RED),
+    ("rosybrown", ROSY_BROWN),
+    ("royalblue", ROYAL_BLUE),
+    ("saddlebrown", SADDLE_BROWN),
+    ("salmon", SALMON),
+    ("sandybrown", SANDY_BROWN),
+    ("seagreen", SEA_GREEN),
+    ("seashell", SEA_SHELL),
+    ("sienna", SIENNA),
+    ("silver", SILVER),
+    ("skyblue", SKY_BLUE),
+    ("slateblue", SLATE_BLUE),
+    ("slategray", SLATE_GRAY),
+    ("slategrey", SLATE_GREY),
+    ("snow", SNOW),
+    ("springgreen", SPRING_GREEN),
+    ("steelblue", STEEL_BLUE),
+    ("tan", TAN),
+    ("teal", TEAL),
+    ("thistle", THISTLE),
+    ("tomato", TOMATO),
+    ("turquoise", TURQUOISE),
+    ("violet", VIOLET),
+    ("wheat", WHEAT),
+    ("white", WHITE),
+    ("whitesmoke", WHITE_SMOKE),
+    ("yellow", YELLOW),
+    ("yellowgreen", YELLOW_GREEN),
+];
+
+/// Returns an iterator over the names and the values of all the named
+/// colors, in alphabetical order of names.
+#[inline]
+pub fn iter() -> slice::Iter<'static, (&'static str, Rgb)> {
+    NAMES.iter()
+}
+
+// Returns `true` if `key` is `name`, ignoring case, `_`, `-` and spaces.
+fn matches(key: &str, name: &str) -> bool {
+    let normalized = key.chars()
+        .filter(|&c| c != '_' && c != '-' && c != ' ')
+        .map(|c| c.to_ascii_lowercase());
+    normalized.eq(name.chars())
+}
+
+/// Finds the color of `name`.
+///
+/// Case, `_`, `-` and spaces in `name` are ignored.
+#[inline]
+pub fn find(name: &str) -> Option<Rgb> {
+    iter().find(|&&(n, _)| matches(name, n)).map(|&(_, c)| c)
+}
+
+// The named colors in CIE L*a*b*, in the order of `NAMES`. Computed once,
+// on first use.
+fn labs() -> &'static [Vec3] {
+    static LABS: OnceLock<Vec<Vec3>> = OnceLock::new();
+    LABS.get_or_init(|| {
+        iter().map(|&(_, c)| *from_rgb::<Lab>(c).as_vec3()).collect()
+    })
+}
+
+/// Returns at most `n` named colors that are closest to `clr`, from the
+/// closest to the farthest.
+///
+/// The distance is the Euclidean distance in the CIE L\*a\*b\* color space.
+pub fn nearest(clr: Rgb, n: usize) -> Vec<(&'static str, Rgb)> {
+    let lab: Lab = from_rgb(clr);
+    let mut dists: Vec<(f32, &'static str, Rgb)> = iter().zip(labs()).map(|(&(name, c), l)| {
+        (distance(*lab.as_vec3(), *l), name, c)
+    }).collect();
+    // stable, so aliases keep the alphabetical order.
+    dists.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    dists.iter().take(n).map(|&(_, name, c)| (name, c)).collect()
+}
+
+impl Rgb {
+    /// Constructs a `Rgb` value from the name of a color constant.
+    ///
+    /// Case, `_`, `-` and spaces in `name` are ignored. Returns `None` if
+    /// `name` is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// assert_eq!(Rgb::from_name("cornflower blue"), Some(CORNFLOWER_BLUE));
+    /// assert_eq!(Rgb::from_name("CORNFLOWER_BLUE"), Some(CORNFLOWER_BLUE));
+    /// assert_eq!(Rgb::from_name("cornflour"), None);
+    /// ```
+    #[inline]
+    pub fn from_name(name: &str) -> Option<Rgb> {
+        find(name)
+    }
+
+    /// Returns the name of the named color that is closest to _self_.
+    ///
+    /// See `names::nearest()` for the distance.
+    #[inline]
+    pub fn nearest_name(&self) -> &'static str {
+        nearest(*self, 1)[0].0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use rgb::consts::*;
+    use space::from_rgb;
+    use lab::Lab;
+    use super::{ iter, find, nearest };
+    use quickcheck::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("red"), Some(RED));
+        assert_eq!(find("RED"), Some(RED));
+        assert_eq!(find("light_goldenrod-yellow"), Some(LIGHT_GOLDEN_ROD_YELLOW));
+        assert_eq!(find("Light Golden Rod Yellow"), Some(LIGHT_GOLDEN_ROD_YELLOW));
+        assert_eq!(find("grey"), find("gray"));
+        assert_eq!(find(""), None);
+        assert_eq!(find("reds"), None);
+        assert_eq!(find("re"), None);
+        assert!(iter().all(|&(n, c)| find(n) == Some(c)));
+    }
+
+    #[test]
+    fn test_iter() {
+        let names: Vec<&str> = iter().map(|&(n, _)| n).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+        assert!(names.iter().all(|n| n.chars().all(|c| c.is_ascii_lowercase())));
+    }
+
+    #[test]
+    fn test_nearest() {
+        assert!(iter().all(|&(_, c)| nearest(c, 1)[0].1 == c));
+        assert_eq!(nearest(BLACK, 200).len(), iter().count());
+        assert_eq!(nearest(BLACK, 0).len(), 0);
+        fn prop(clr: Rgb) -> bool {
+            let ns = nearest(clr, 3);
+            let lab: Lab = from_rgb(clr);
+            let d = |c: Rgb| -> f32 {
+                distance(*lab.as_vec3(), *from_rgb::<Lab>(c).as_vec3())
+            };
+            ns.len() == 3 &&
+            d(ns[0].1) <= d(ns[1].1) && d(ns[1].1) <= d(ns[2].1) &&
+            iter().all(|&(_, c)| d(c) >= d(ns[0].1)) &&
+            clr.nearest_name() == ns[0].0
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+}
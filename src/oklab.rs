@@ -0,0 +1,363 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use glm::ext::*;
+use super::space::{ ColorSpace, Pod3, from_rgb };
+use super::rgb::Rgb;
+use std::mem;
+use rand::{ Rand, Rng };
+
+/// The Oklab color space.
+///
+/// # See
+///
+/// - [A perceptual color space for image processing](https://bottosson.github.io/posts/oklab/),
+///   from where the numbers used in conversion are got.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let white: Oklab = from_rgb(WHITE);
+/// assert!((white.lightness() - 1.).abs() < 0.0001);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Oklab {
+    l: f32,
+    a: f32,
+    b: f32
+}
+
+impl Oklab {
+    /// Constructs an `Oklab` value.
+    ///
+    /// Parameter `l` is clampped to the range *[0, 1]*. `a` and `b` are not
+    /// bounded.
+    #[inline]
+    pub fn new(l: f32, a: f32, b: f32) -> Oklab {
+        Oklab { l: clamp(l, 0., 1.), a: a, b: b }
+    }
+
+    /// Returns the lightness of _self_, which is a value in the range
+    /// *[0, 1]*.
+    #[inline]
+    pub fn lightness(&self) -> f32 {
+        self.l
+    }
+
+    /// Returns the `a` component of _self_, i.e., how green/red the color is.
+    #[inline]
+    pub fn a(&self) -> f32 {
+        self.a
+    }
+
+    /// Returns the `b` component of _self_, i.e., how blue/yellow the color
+    /// is.
+    #[inline]
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    /// Re-interprets a reference of `Oklab` to `Vec3`.
+    #[inline]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let v: &Vec3 = unsafe { mem::transmute(self) };
+        v
+    }
+}
+
+impl Eq for Oklab {}
+
+impl ApproxEq for Oklab {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Oklab, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+impl Rand for Oklab {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Oklab {
+        // only colors in the RGB gamut.
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+unsafe impl Pod3 for Oklab {}
+
+// The matrices keep the digits published by Björn Ottosson.
+#[allow(clippy::excessive_precision)]
+impl ColorSpace for Oklab {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Oklab {
+        // column major.
+        let lms_mat = mat3(
+            0.4122214708, 0.2119034982, 0.0883024619,
+            0.5363325363, 0.6806995451, 0.2817188376,
+            0.0514459929, 0.1073969566, 0.6299787005
+        );
+        let lab_mat = mat3(
+             0.2104542553,  1.9779984951,  0.0259040371,
+             0.7936177850, -2.4285922050,  0.7827717662,
+            -0.0040720468,  0.4505937099, -0.8086757660
+        );
+        let lms = cbrt(lms_mat.mul_v(rgb.as_vec3()));
+        let v = lab_mat.mul_v(&lms);
+        Oklab { l: v.x, a: v.y, b: v.z }
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        let lms_mat = mat3(
+            1.,            1.,            1.,
+            0.3963377774, -0.1055613458, -0.0894841775,
+            0.2158037573, -0.0638541728, -1.2914855480
+        );
+        let rgb_mat = mat3(
+             4.0767416621, -1.2684380046, -0.0041960863,
+            -3.3077115913,  2.6097574011, -0.7034186147,
+             0.2309699292, -0.3413193965,  1.7076147010
+        );
+        let lms = lms_mat.mul_v(self.as_vec3());
+        let v = rgb_mat.mul_v(&(lms * lms * lms));
+        Rgb::new(v.x, v.y, v.z)
+    }
+}
+
+/// Equivalent to `Oklab::new()`.
+#[inline]
+pub fn oklab(l: f32, a: f32, b: f32) -> Oklab {
+    Oklab::new(l, a, b)
+}
+
+/// The Oklch color space, i.e., the cylindrical representation of Oklab.
+///
+/// Hue is an angle in radians in the interval _[0, 2π)_, as `Hsv`'s.
+///
+/// # Note
+///
+/// Not every `Oklch` value is in the RGB gamut. Out of gamut colors are
+/// clampped when converted to `Rgb`.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Oklch {
+    l: f32,
+    c: f32,
+    h: f32
+}
+
+impl Oklch {
+    /// Constructs an `Oklch` value from given `lightness`, `chroma` and
+    /// `hue`.
+    ///
+    /// Parameter `lightness` is clampped to the interval _[0, 1]_, negative
+    /// `chroma` is clampped to `0`, and `hue` is clampped to the interval
+    /// _[0, 2π)_.
+    #[inline]
+    pub fn new(lightness: f32, chroma: f32, hue: f32) -> Oklch {
+        let mut clr = Oklch { l: clamp(lightness, 0., 1.), c: max(chroma, 0.), h: 0. };
+        clr.set_hue(hue);
+        clr
+    }
+
+    /// Converts an `Oklab` value to `Oklch`.
+    #[inline]
+    pub fn from_oklab(lab: Oklab) -> Oklch {
+        let c = sqrt(lab.a * lab.a + lab.b * lab.b);
+        let mut clr = Oklch { l: lab.l, c: c, h: 0. };
+        // for gray colors, hue is irrevalent.
+        if !is_approx_eq(&c, &0.) {
+            let h = lab.b.atan2(lab.a);
+            clr.set_hue(if h < 0. { h + f32::tau() } else { h });
+        }
+        clr
+    }
+
+    /// Converts _self_ to `Oklab`.
+    #[inline]
+    pub fn to_oklab(&self) -> Oklab {
+        Oklab { l: self.l, a: self.c * cos(self.h), b: self.c * sin(self.h) }
+    }
+
+    /// Returns the lightness of _self_.
+    #[inline]
+    pub fn lightness(&self) -> f32 {
+        self.l
+    }
+
+    /// Returns the chroma of _self_.
+    #[inline]
+    pub fn chroma(&self) -> f32 {
+        self.c
+    }
+
+    /// Returns the hue of _self_.
+    #[inline]
+    pub fn hue(&self) -> f32 {
+        self.h
+    }
+
+    /// Changes _self_'s lightness value to `l`.
+    ///
+    /// The parameter `l` is clampped to the range [0, 1].
+    #[inline]
+    pub fn set_lightness(&mut self, l: f32) {
+        self.l = clamp(l, 0., 1.);
+    }
+
+    /// Returns a new `Oklch` value with given lightness value `l`, and chroma
+    /// and hue values from _self_.
+    #[inline]
+    pub fn with_lightness(&self, l: f32) -> Oklch {
+        let mut c = *self;
+        c.set_lightness(l);
+        c
+    }
+
+    /// Changes _self_'s chroma value to `c`.
+    ///
+    /// Negative `c` is clampped to `0`.
+    #[inline]
+    pub fn set_chroma(&mut self, c: f32) {
+        self.c = max(c, 0.);
+    }
+
+    /// Returns a new `Oklch` value with given chroma value `c`, and lightness
+    /// and hue values from _self_.
+    #[inline]
+    pub fn with_chroma(&self, c: f32) -> Oklch {
+        let mut clr = *self;
+        clr.set_chroma(c);
+        clr
+    }
+
+    /// Changes _self_'s hue value to `h`.
+    ///
+    /// The parameter `h` is clampped to the range [0, 2π).
+    #[inline]
+    pub fn set_hue(&mut self, h: f32) {
+        let pi2 = tau();
+        let mut hv = clamp(h, 0., pi2);
+        if hv == pi2 {
+            hv = 0.;
+        }
+        self.h = hv
+    }
+
+    /// Returns a new `Oklch` value with given hue value `h`, and lightness
+    /// and chroma values from _self_.
+    #[inline]
+    pub fn with_hue(&self, h: f32) -> Oklch {
+        let mut c = *self;
+        c.set_hue(h);
+        c
+    }
+
+    /// Re-interprets a reference of `Oklch` to `Vec3`.
+    #[inline]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let v: &Vec3 = unsafe { mem::transmute(self) };
+        v
+    }
+}
+
+impl Eq for Oklch {}
+
+impl ApproxEq for Oklch {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Oklch, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+impl Rand for Oklch {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Oklch {
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+unsafe impl Pod3 for Oklch {}
+
+impl ColorSpace for Oklch {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Oklch {
+        Oklch::from_oklab(from_rgb(rgb))
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        self.to_oklab().to_rgb()
+    }
+}
+
+/// Equivalent to `Oklch::new()`.
+#[inline]
+pub fn oklch(l: f32, c: f32, h: f32) -> Oklch {
+    Oklch::new(l, c, h)
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use rgb::consts::{ BLACK, WHITE };
+    use super::{ Oklab, Oklch };
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let lab: Oklab = ColorSpace::from_rgb(clr);
+            let rgb = lab.to_rgb();
+            is_close_to(&rgb, &clr, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_oklch_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let lch: Oklch = ColorSpace::from_rgb(clr);
+            let rgb = lch.to_rgb();
+            lch.hue() >= 0. &&
+            lch.hue() < ext::tau() &&
+            is_close_to(&rgb, &clr, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_white_black() {
+        let white: Oklab = ColorSpace::from_rgb(WHITE);
+        assert!(is_close_to(&white, &Oklab::new(1., 0., 0.), 0.0001));
+        let black: Oklab = ColorSpace::from_rgb(BLACK);
+        assert_eq!(black, Oklab::new(0., 0., 0.));
+    }
+}
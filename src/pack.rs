@@ -0,0 +1,408 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+
+//! Packing colors into, and unpacking colors from, pixel formats used by
+//! GPUs and frame buffers.
+//!
+//! Packed pixels are stored in the low bits of a `u64`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::*;
+//! use glm_color::pack::{ PixelFormat, Encoding };
+//!
+//! let px = RED.pack(PixelFormat::Rgb565, Encoding::Linear);
+//! assert_eq!(px, 0xF800);
+//! assert_eq!(Rgb::unpack(px, PixelFormat::Rgb565, Encoding::Linear), RED);
+//!
+//! let half = Rgba::new(WHITE, 0.5);
+//! assert_eq!(half.pack(PixelFormat::Argb8888, Encoding::Srgb), 0x80FFFFFF);
+//! ```
+
+use glm::*;
+use super::space::{ ColorSpace, from_rgb };
+use super::rgb::Rgb;
+use super::srgb::Srgb;
+use super::alpha::{ Alpha, Rgba };
+use super::quantize::{ Rounding, quantize, dequantize };
+
+/// Pixel formats.
+///
+/// The first 5 formats are unsigned normalized integers, with the first
+/// channel in the name in the most significant bits.
+///
+/// The others follow the conventions of OpenGL and Direct3D, with red in the
+/// least significant bits.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PixelFormat {
+    /// 8-bit red, green, blue and alpha, as `0xRRGGBBAA`.
+    Rgba8888,
+    /// 8-bit blue, green, red and alpha, as `0xBBGGRRAA`.
+    Bgra8888,
+    /// 8-bit alpha, red, green and blue, as `0xAARRGGBB`.
+    Argb8888,
+    /// 5-bit red, 6-bit green and 5-bit blue, with red in bits 11 to 15.
+    Rgb565,
+    /// 4-bit red, green, blue and alpha, as `0xRGBA`.
+    Rgba4444,
+    /// 10-bit red, green and blue, and 2-bit alpha, with red in bits 0 to 9
+    /// and alpha in bits 30 and 31.
+    Rgb10A2,
+    /// Unsigned 11-bit floating point red and green, and 10-bit floating
+    /// point blue, with red in bits 0 to 10.
+    R11G11B10F,
+    /// 9-bit red, green and blue mantissas, with red in bits 0 to 8, and a
+    /// shared 5-bit exponent in bits 27 to 31.
+    Rgb9E5,
+    /// 16-bit half precision floating point red, green, blue and alpha,
+    /// with red in bits 0 to 15.
+    Rgba16F,
+}
+
+/// Encodings of color channels in pixels.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    /// Color channels are linear RGB values.
+    Linear,
+    /// Color channels are encoded with the sRGB transfer function. Alpha is
+    /// always linear.
+    Srgb,
+}
+
+impl PixelFormat {
+    /// Returns the number of bits of a pixel in format _self_.
+    #[inline]
+    pub fn bits(&self) -> u32 {
+        match *self {
+            PixelFormat::Rgb565 | PixelFormat::Rgba4444 => 16,
+            PixelFormat::Rgba16F => 64,
+            _ => 32,
+        }
+    }
+
+    /// Returns `true` if format _self_ has an alpha channel.
+    #[inline]
+    pub fn has_alpha(&self) -> bool {
+        !matches!(*self, PixelFormat::Rgb565 | PixelFormat::R11G11B10F | PixelFormat::Rgb9E5)
+    }
+
+    // (bits, shift) of red, green, blue and alpha channels.
+    //
+    // Alpha has `0` bits if there is no alpha channel. Not for `Rgb9E5`.
+    fn layout(&self) -> [(u32, u32); 4] {
+        match *self {
+            PixelFormat::Rgba8888 => [(8, 24), (8, 16), (8, 8), (8, 0)],
+            PixelFormat::Bgra8888 => [(8, 8), (8, 16), (8, 24), (8, 0)],
+            PixelFormat::Argb8888 => [(8, 16), (8, 8), (8, 0), (8, 24)],
+            PixelFormat::Rgb565 => [(5, 11), (6, 5), (5, 0), (0, 0)],
+            PixelFormat::Rgba4444 => [(4, 12), (4, 8), (4, 4), (4, 0)],
+            PixelFormat::Rgb10A2 => [(10, 0), (10, 10), (10, 20), (2, 30)],
+            PixelFormat::R11G11B10F => [(11, 0), (11, 11), (10, 22), (0, 0)],
+            PixelFormat::Rgba16F => [(16, 0), (16, 16), (16, 32), (16, 48)],
+            PixelFormat::Rgb9E5 => unreachable!(),
+        }
+    }
+
+    // Encodes a channel value `v` in [0, 1] to `bits` bits.
+    #[inline]
+    fn encode(&self, v: f32, bits: u32) -> u64 {
+        match *self {
+            PixelFormat::R11G11B10F => encode_float(v, bits - 5) as u64,
+            // the sign bit of positive values is `0`.
+            PixelFormat::Rgba16F => encode_float(v, 10) as u64,
+            _ => quantize(v, bits, Rounding::Nearest) as u64,
+        }
+    }
+
+    #[inline]
+    fn decode(&self, q: u64, bits: u32) -> f32 {
+        match *self {
+            PixelFormat::R11G11B10F => decode_float(q as u32, bits - 5),
+            PixelFormat::Rgba16F => {
+                let v = decode_float(q as u32 & 0x7FFF, 10);
+                if q & 0x8000 == 0 { v } else { -v }
+            }
+            _ => dequantize(q as u16, bits),
+        }
+    }
+
+    fn pack(&self, clr: Rgba, encoding: Encoding) -> u64 {
+        let rgb =
+            match encoding {
+                Encoding::Linear => *clr.color().as_vec3(),
+                Encoding::Srgb => *from_rgb::<Srgb>(clr.color()).as_vec3(),
+            };
+        if *self == PixelFormat::Rgb9E5 {
+            return encode_rgb9e5(rgb);
+        }
+        let v = [rgb.x, rgb.y, rgb.z, clr.alpha()];
+        self.layout().iter().zip(v.iter()).fold(0, |px, (&(bits, shift), &c)| {
+            if bits == 0 { px } else { px | (self.encode(c, bits) << shift) }
+        })
+    }
+
+    fn unpack(&self, px: u64, encoding: Encoding) -> Rgba {
+        let (rgb, alpha) =
+            if *self == PixelFormat::Rgb9E5 {
+                (decode_rgb9e5(px), 1.)
+            } else {
+                let layout = self.layout();
+                let c = |i: usize| -> f32 {
+                    let (bits, shift) = layout[i];
+                    if bits == 0 {
+                        1.
+                    } else {
+                        self.decode((px >> shift) & ((1u64 << bits) - 1), bits)
+                    }
+                };
+                (vec3(c(0), c(1), c(2)), c(3))
+            };
+        let clr =
+            match encoding {
+                Encoding::Linear => Rgb::new(rgb.x, rgb.y, rgb.z),
+                Encoding::Srgb => Srgb::new(rgb.x, rgb.y, rgb.z).to_rgb(),
+            };
+        Alpha::new(clr, alpha)
+    }
+}
+
+// Shifts `x` right by `s` bits, rounding to nearest even.
+#[inline]
+fn round_shift(x: u32, s: u32) -> u32 {
+    if s == 0 {
+        x
+    } else if s >= 32 {
+        0
+    } else {
+        let q = x >> s;
+        let r = x & ((1 << s) - 1);
+        let half = 1 << (s - 1);
+        if r > half || (r == half && q & 1 == 1) { q + 1 } else { q }
+    }
+}
+
+// Encodes non-negative `v` to an unsigned floating point number with 5
+// exponent bits and `m` mantissa bits, rounding to nearest even.
+//
+// Values that are too large are saturated to the maximum finite value.
+fn encode_float(v: f32, m: u32) -> u32 {
+    let max = (30 << m) | ((1 << m) - 1);
+    if v.is_nan() || v <= 0. {
+        return 0;
+    }
+    let bits = v.to_bits();
+    let exp = ((bits >> 23) & 0xFF) as i32 - 127 + 15;
+    let mantissa = bits & 0x7FFFFF;
+    let q =
+        if exp <= 0 {
+            // denormal.
+            round_shift(mantissa | 0x800000, 23 - m + (1 - exp) as u32)
+        } else if exp >= 31 {
+            max + 1
+        } else {
+            // a carry of the mantissa goes to the exponent.
+            round_shift(((exp as u32) << 23) | mantissa, 23 - m)
+        };
+    if q > max { max } else { q }
+}
+
+// Decodes an unsigned floating point number `q` with 5 exponent bits and
+// `m` mantissa bits.
+fn decode_float(q: u32, m: u32) -> f32 {
+    let exp = (q >> m) as i32;
+    let mantissa = (q & ((1 << m) - 1)) as f32;
+    let scale = (1u32 << m) as f32;
+    if exp == 0 {
+        mantissa / scale * 2f32.powi(-14)
+    } else if exp == 31 {
+        if mantissa == 0. { f32::INFINITY } else { f32::NAN }
+    } else {
+        (1. + mantissa / scale) * 2f32.powi(exp - 15)
+    }
+}
+
+// Exponent bias, mantissa bits and maximum exponent of RGB9E5.
+const E5_BIAS: i32 = 15;
+const E5_MANTISSA: i32 = 9;
+const E5_MAX: i32 = 31;
+
+// See the `EXT_texture_shared_exponent` extension of OpenGL.
+fn encode_rgb9e5(rgb: Vec3) -> u64 {
+    let max_value = 511. / 512. * 2f32.powi(E5_MAX - E5_BIAS);
+    let c = clamp_s(rgb, 0., max_value);
+    let maxc = c.max();
+    let floor_log2 =
+        if maxc > 0. {
+            ((maxc.to_bits() >> 23) & 0xFF) as i32 - 127
+        } else {
+            -E5_BIAS - 1
+        };
+    let mut exp = max(-E5_BIAS - 1, floor_log2) + 1 + E5_BIAS;
+    let scale = |e: i32| -> f32 { 2f32.powi(e - E5_BIAS - E5_MANTISSA) };
+    if floor(maxc / scale(exp) + 0.5) == 512. {
+        exp += 1;
+    }
+    let s = c.map(|x| floor(x / scale(exp) + 0.5));
+    (s.x as u64) | ((s.y as u64) << 9) | ((s.z as u64) << 18) | ((exp as u64) << 27)
+}
+
+fn decode_rgb9e5(px: u64) -> Vec3 {
+    let exp = ((px >> 27) & 0x1F) as i32;
+    let scale = 2f32.powi(exp - E5_BIAS - E5_MANTISSA);
+    let m = |shift: u64| -> f32 { ((px >> shift) & 0x1FF) as f32 * scale };
+    vec3(m(0), m(9), m(18))
+}
+
+impl Rgb {
+    /// Packs _self_ into a pixel in `format`, with color channels encoded in
+    /// `encoding`.
+    ///
+    /// The alpha channel, if there is one in `format`, is `1`.
+    #[inline]
+    pub fn pack(&self, format: PixelFormat, encoding: Encoding) -> u64 {
+        format.pack(Alpha::opaque(*self), encoding)
+    }
+
+    /// Unpacks a `Rgb` value from pixel `px` in `format`, whose color
+    /// channels are encoded in `encoding`.
+    ///
+    /// The alpha channel is discarded. Bits higher than `format.bits()` are
+    /// ignored.
+    #[inline]
+    pub fn unpack(px: u64, format: PixelFormat, encoding: Encoding) -> Rgb {
+        format.unpack(px, encoding).color()
+    }
+}
+
+impl Alpha<Rgb> {
+    /// Packs _self_ into a pixel in `format`, with color channels encoded in
+    /// `encoding`.
+    ///
+    /// The alpha value is discarded if `format` does not have an alpha
+    /// channel.
+    #[inline]
+    pub fn pack(&self, format: PixelFormat, encoding: Encoding) -> u64 {
+        format.pack(*self, encoding)
+    }
+
+    /// Unpacks a `Rgba` value from pixel `px` in `format`, whose color
+    /// channels are encoded in `encoding`.
+    ///
+    /// The alpha value is `1` if `format` does not have an alpha channel.
+    #[inline]
+    pub fn unpack(px: u64, format: PixelFormat, encoding: Encoding) -> Rgba {
+        format.unpack(px, encoding)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use rgb::Rgb;
+    use rgb::consts::*;
+    use alpha::Rgba;
+    use super::{ PixelFormat, Encoding, encode_float, decode_float };
+    use super::PixelFormat::*;
+    use quickcheck::*;
+
+    const FORMATS: [PixelFormat; 9] = [
+        Rgba8888, Bgra8888, Argb8888, Rgb565, Rgba4444, Rgb10A2, R11G11B10F,
+        Rgb9E5, Rgba16F
+    ];
+
+    #[test]
+    fn test_layout() {
+        let clr = Rgba::new(Rgb::from_u32(0x123456), 0x78 as f32 / 255.);
+        let pack = |f: PixelFormat| -> u64 { clr.pack(f, Encoding::Linear) };
+        assert_eq!(pack(Rgba8888), 0x12345678);
+        assert_eq!(pack(Bgra8888), 0x56341278);
+        assert_eq!(pack(Argb8888), 0x78123456);
+        assert_eq!(RED.pack(Rgb565, Encoding::Linear), 0xF800);
+        assert_eq!(GREEN.pack(Rgb565, Encoding::Linear), 0x07E0);
+        assert_eq!(Rgba::new(BLUE, 0.).pack(Rgba4444, Encoding::Linear), 0x00F0);
+        assert_eq!(RED.pack(Rgb10A2, Encoding::Linear), 0xC00003FF);
+        assert_eq!(WHITE.pack(R11G11B10F, Encoding::Linear), 0x781E03C0);
+        assert_eq!(RED.pack(Rgb9E5, Encoding::Linear), (16 << 27) | 256);
+        assert_eq!(Rgba::new(WHITE, 0.5).pack(Rgba16F, Encoding::Linear),
+                   0x3800_3C00_3C00_3C00);
+        assert!(FORMATS.iter().all(|&f| WHITE.pack(f, Encoding::Srgb).checked_shr(f.bits()).unwrap_or(0) == 0));
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(encode_float(1., 10), 0x3C00);
+        assert_eq!(encode_float(65504., 10), 0x7BFF);
+        assert_eq!(encode_float(1e10, 10), 0x7BFF);
+        assert_eq!(encode_float(2f32.powi(-24), 10), 1);
+        assert_eq!(encode_float(2f32.powi(-26), 10), 0);
+        assert_eq!(encode_float(1. + 2f32.powi(-11), 10), 0x3C00);
+        assert_eq!(encode_float(1. + 3. * 2f32.powi(-11), 10), 0x3C02);
+        assert_eq!(decode_float(0x7C00, 10), f32::INFINITY);
+        for m in [5, 6, 10].iter() {
+            // every finite value survives a round trip.
+            for q in 0..(31 << m) {
+                assert_eq!(encode_float(decode_float(q, *m), *m), q);
+            }
+        }
+    }
+
+    #[test]
+    fn test_u8_round_trip() {
+        fn prop(px: u32) -> bool {
+            let px = px as u64;
+            [Rgba8888, Bgra8888, Argb8888].iter().all(|&f| {
+                Rgba::unpack(px, f, Encoding::Linear).pack(f, Encoding::Linear) == px &&
+                Rgba::unpack(px, f, Encoding::Srgb).pack(f, Encoding::Srgb) == px
+            }) &&
+            Rgba::unpack(px & 0xFFFF, Rgb565, Encoding::Linear).pack(Rgb565, Encoding::Linear) == px & 0xFFFF &&
+            Rgba::unpack(px, Rgb10A2, Encoding::Linear).pack(Rgb10A2, Encoding::Linear) == px
+        }
+        quickcheck(prop as fn(u32) -> bool);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        fn prop(clr: Rgb, a: f32) -> bool {
+            let rgba = Rgba::new(clr, a.fract().abs());
+            FORMATS.iter().all(|&f| {
+                // maximum error of the channels.
+                let eps = match f {
+                    Rgb565 => 1. / 62.,
+                    Rgba4444 => 1. / 30.,
+                    R11G11B10F => 1. / 32.,
+                    Rgb9E5 => 1. / 512.,
+                    Rgba16F => 1. / 2048.,
+                    _ => 1. / 510.,
+                };
+                let alpha = if f.has_alpha() { rgba.alpha() } else { 1. };
+                let expected = Rgba::new(clr, if f == Rgb10A2 { round(alpha * 3.) / 3. } else { alpha });
+                // the slope of the sRGB decoding is at most 2.4.
+                Rgba::unpack(rgba.pack(f, Encoding::Linear), f, Encoding::Linear).is_close_to(&expected, eps) &&
+                Rgba::unpack(rgba.pack(f, Encoding::Srgb), f, Encoding::Srgb).is_close_to(&expected, eps * 2.4)
+            })
+        }
+        quickcheck(prop as fn(Rgb, f32) -> bool);
+    }
+}
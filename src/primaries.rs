@@ -0,0 +1,297 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Matrices between RGB color spaces and CIE XYZ, derived from chromaticities.
+//!
+//! An RGB color space is defined by the chromaticities of its three
+//! primaries and its white point. `Primaries` derives from them the
+//! _normalized primary matrix_, which converts linear RGB values to CIE XYZ
+//! values whose `Y` of the white point is `1`, and its inverse.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate glm;
+//! # extern crate glm_color;
+//! # fn main() {
+//! use glm::*;
+//! use glm_color::primaries::*;
+//!
+//! // a camera specific RGB space.
+//! let cam = Primaries::new(vec2(0.69, 0.31), vec2(0.23, 0.75), vec2(0.14, 0.04), D65_XY);
+//! let m = cam.rgb_to_xyz();
+//! // white maps to the white point.
+//! let w = m * vec3(1., 1., 1.);
+//! assert!(is_close_to(&w.y, &1., 0.00001));
+//! assert!(is_close_to(&(w.x / w.sum()), &0.3127, 0.00001));
+//! # }
+//! ```
+//!
+//! # See
+//!
+//! - SMPTE RP 177-1993, _Derivation of Basic Television Color Equations_.
+//! - [RGB/XYZ matrices](http://www.brucelindbloom.com/index.html?Eqn_RGB_XYZ_Matrix.html)
+//!   in Bruce Lindbloom's site.
+
+use glm::*;
+
+pub use super::illuminant::{ D65_XY, D50_XY };
+
+/// Chromaticity of the white point of ACES, which is close to D60.
+pub const ACES_XY: Vec2 = Vec2 { x: 0.32168, y: 0.33767 };
+
+/// Chromaticities of the primaries and the white point of an RGB color
+/// space.
+///
+/// The chromaticities are CIE 1931 _xy_ coordinates.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Primaries {
+    red: Vec2,
+    green: Vec2,
+    blue: Vec2,
+    white: Vec2
+}
+
+/// sRGB and ITU-R BT.709, i.e., the primaries of `Rgb`.
+pub const SRGB: Primaries = Primaries {
+    red: Vec2 { x: 0.64, y: 0.33 },
+    green: Vec2 { x: 0.30, y: 0.60 },
+    blue: Vec2 { x: 0.15, y: 0.06 },
+    white: D65_XY
+};
+
+/// Display P3.
+pub const DISPLAY_P3: Primaries = Primaries {
+    red: Vec2 { x: 0.680, y: 0.320 },
+    green: Vec2 { x: 0.265, y: 0.690 },
+    blue: Vec2 { x: 0.150, y: 0.060 },
+    white: D65_XY
+};
+
+/// Adobe RGB (1998).
+pub const ADOBE_RGB: Primaries = Primaries {
+    red: Vec2 { x: 0.64, y: 0.33 },
+    green: Vec2 { x: 0.21, y: 0.71 },
+    blue: Vec2 { x: 0.15, y: 0.06 },
+    white: D65_XY
+};
+
+/// ITU-R BT.2020.
+pub const REC2020: Primaries = Primaries {
+    red: Vec2 { x: 0.708, y: 0.292 },
+    green: Vec2 { x: 0.170, y: 0.797 },
+    blue: Vec2 { x: 0.131, y: 0.046 },
+    white: D65_XY
+};
+
+/// ProPhoto RGB.
+pub const PROPHOTO_RGB: Primaries = Primaries {
+    red: Vec2 { x: 0.734699, y: 0.265301 },
+    green: Vec2 { x: 0.159597, y: 0.840403 },
+    blue: Vec2 { x: 0.036598, y: 0.000105 },
+    white: D50_XY
+};
+
+/// ACES AP0, the primaries of ACES2065-1.
+pub const ACES_AP0: Primaries = Primaries {
+    red: Vec2 { x: 0.7347, y: 0.2653 },
+    green: Vec2 { x: 0.0, y: 1.0 },
+    blue: Vec2 { x: 0.0001, y: -0.0770 },
+    white: ACES_XY
+};
+
+/// ACES AP1, the primaries of ACEScg.
+pub const ACES_AP1: Primaries = Primaries {
+    red: Vec2 { x: 0.713, y: 0.293 },
+    green: Vec2 { x: 0.165, y: 0.830 },
+    blue: Vec2 { x: 0.128, y: 0.044 },
+    white: ACES_XY
+};
+
+// CIE XYZ of chromaticity `xy`, with `Y` being `1`.
+#[inline]
+fn xy_to_xyz(xy: Vec2) -> DVec3 {
+    let (x, y) = (xy.x as f64, xy.y as f64);
+    dvec3(x / y, 1., (1. - x - y) / y)
+}
+
+#[inline]
+fn to_mat3(m: &DMat3) -> Mat3 {
+    let c = |i: usize| -> Vec3 { vec3(m[i].x as f32, m[i].y as f32, m[i].z as f32) };
+    Mat3 { c0: c(0), c1: c(1), c2: c(2) }
+}
+
+impl Primaries {
+    /// Constructs a `Primaries` value from chromaticities of the `red`,
+    /// `green` and `blue` primaries, and the `white` point.
+    ///
+    /// # Panics
+    ///
+    /// If any `y` coordinate is `0`, or the primaries are collinear.
+    #[inline]
+    pub fn new(red: Vec2, green: Vec2, blue: Vec2, white: Vec2) -> Primaries {
+        let p = Primaries { red: red, green: green, blue: blue, white: white };
+        let d = p.npm().determinant();
+        assert!(d != 0. && d.is_finite(), "degenerated primaries.");
+        p
+    }
+
+    /// Returns the chromaticity of the red primary.
+    #[inline]
+    pub fn red(&self) -> Vec2 {
+        self.red
+    }
+
+    /// Returns the chromaticity of the green primary.
+    #[inline]
+    pub fn green(&self) -> Vec2 {
+        self.green
+    }
+
+    /// Returns the chromaticity of the blue primary.
+    #[inline]
+    pub fn blue(&self) -> Vec2 {
+        self.blue
+    }
+
+    /// Returns the chromaticity of the white point.
+    #[inline]
+    pub fn white(&self) -> Vec2 {
+        self.white
+    }
+
+    /// Returns a new `Primaries` value with given `white` point, and the
+    /// primaries of _self_.
+    #[inline]
+    pub fn with_white(&self, white: Vec2) -> Primaries {
+        Primaries::new(self.red, self.green, self.blue, white)
+    }
+
+    /// Returns CIE XYZ tristimulus values of the white point, normalized so
+    /// that `Y` is `1`.
+    #[inline]
+    pub fn white_xyz(&self) -> Vec3 {
+        let w = xy_to_xyz(self.white);
+        vec3(w.x as f32, w.y as f32, w.z as f32)
+    }
+
+    // The normalized primary matrix, in double precision.
+    fn npm(&self) -> DMat3 {
+        let p = DMat3 {
+            c0: xy_to_xyz(self.red),
+            c1: xy_to_xyz(self.green),
+            c2: xy_to_xyz(self.blue)
+        };
+        match p.inverse() {
+            Some(inv) => {
+                // scales of the primaries so that they add up to white.
+                let s = inv * xy_to_xyz(self.white);
+                DMat3 { c0: p.c0 * s.x, c1: p.c1 * s.y, c2: p.c2 * s.z }
+            }
+            None => DMat3 { c0: p.c0 * 0., c1: p.c1 * 0., c2: p.c2 * 0. },
+        }
+    }
+
+    /// Returns the normalized primary matrix, which converts linear RGB
+    /// values to CIE XYZ values.
+    ///
+    /// The second row of the matrix is the luminance of the primaries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::primaries::SRGB;
+    ///
+    /// let xyz: Xyz = from_rgb(RED);
+    /// let m = SRGB.rgb_to_xyz();
+    /// assert!((m[0].x - xyz.x()).abs() < 0.0001);
+    /// ```
+    #[inline]
+    pub fn rgb_to_xyz(&self) -> Mat3 {
+        to_mat3(&self.npm())
+    }
+
+    /// Returns the inverse of the normalized primary matrix, which converts
+    /// CIE XYZ values to linear RGB values.
+    #[inline]
+    pub fn xyz_to_rgb(&self) -> Mat3 {
+        to_mat3(&inverse(&self.npm()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use ycbcr::Standard;
+    use super::*;
+
+    fn close(a: &Mat3, b: &Mat3, eps: f32) -> bool {
+        (0..3).all(|i| a[i].is_close_to(&b[i], eps))
+    }
+
+    #[test]
+    fn test_srgb() {
+        // IEC 61966-2-1.
+        let published = mat3(
+            0.4124, 0.2126, 0.0193,
+            0.3576, 0.7152, 0.1192,
+            0.1805, 0.0722, 0.9505
+        );
+        let m = SRGB.rgb_to_xyz();
+        assert!(close(&m, &published, 0.0001));
+        let (kr, kb) = Standard::Bt709.coefficients();
+        assert!(vec3(m[0].y, m[1].y, m[2].y).is_close_to(&vec3(kr, 1. - kr - kb, kb), 0.0001));
+    }
+
+    #[test]
+    fn test_rec2020() {
+        // ITU-R BT.2087.
+        let published = mat3(
+            0.636958, 0.262700, 0.000000,
+            0.144617, 0.677998, 0.028073,
+            0.168881, 0.059302, 1.060985
+        );
+        let m = REC2020.rgb_to_xyz();
+        assert!(close(&m, &published, 0.000001));
+        let (kr, kb) = Standard::Bt2020.coefficients();
+        assert!(vec3(m[0].y, m[1].y, m[2].y).is_close_to(&vec3(kr, 1. - kr - kb, kb), 0.0001));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let all = [SRGB, DISPLAY_P3, ADOBE_RGB, REC2020, PROPHOTO_RGB, ACES_AP0, ACES_AP1];
+        for p in all.iter() {
+            let id = p.xyz_to_rgb() * p.rgb_to_xyz();
+            assert!(close(&id, &mat3(1., 0., 0., 0., 1., 0., 0., 0., 1.), 0.00001));
+            let w = p.rgb_to_xyz() * vec3(1., 1., 1.);
+            assert!(w.is_close_to(&p.white_xyz(), 0.00001));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_degenerated() {
+        Primaries::new(vec2(0.1, 0.1), vec2(0.2, 0.2), vec2(0.3, 0.3), D65_XY);
+    }
+}
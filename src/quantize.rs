@@ -0,0 +1,144 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+
+//! Quantization of channel values to unsigned normalized integers.
+//!
+//! A value `v` in _[0, 1]_ is mapped to an integer `q` of `bits` bits, in
+//! _[0, 2^bits - 1]_, and `q` is mapped back to `q / (2^bits - 1)`.
+//! Dequantized values survive quantization exactly, with both `Nearest`
+//! and `Floor` rounding.
+
+use glm::*;
+use rand::{ Rng, thread_rng };
+
+/// Rounding modes of quantization.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    /// Rounds to the nearest integer, and half way cases away from `0`.
+    Nearest,
+    /// Rounds down.
+    Floor,
+    /// Rounds up or down randomly, with probabilities that make the
+    /// expected result equal to the exact one. Uses the thread local RNG.
+    ///
+    /// It is also known as dithering with white noise, and avoids banding.
+    Stochastic,
+}
+
+// Returns `2^bits - 1`, checking `bits`.
+#[inline]
+fn max_value(bits: u32) -> f32 {
+    assert!((1..=16).contains(&bits), "quantization bits must be in [1, 16]");
+    ((1u32 << bits) - 1) as f32
+}
+
+/// Quantizes `v` to an integer of `bits` bits using rounding mode
+/// `rounding`.
+///
+/// Parameter `v` is clampped to the interval _[0, 1]_.
+///
+/// # Panics
+///
+/// If `bits` is not in the range *[1, 16]*.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::quantize::*;
+///
+/// assert_eq!(quantize(0.5, 8, Rounding::Nearest), 128);
+/// assert_eq!(quantize(0.5, 8, Rounding::Floor), 127);
+/// assert_eq!(quantize(1., 10, Rounding::Floor), 1023);
+/// ```
+#[inline]
+pub fn quantize(v: f32, bits: u32, rounding: Rounding) -> u16 {
+    let max = max_value(bits);
+    let x = clamp(v, 0., 1.) * max;
+    let q =
+        match rounding {
+            Rounding::Nearest => round(x),
+            Rounding::Floor => floor(x),
+            Rounding::Stochastic => {
+                // not `floor(x + r)`, which may round up integers.
+                let mut rng = thread_rng();
+                let q = floor(x);
+                if rng.gen::<f32>() < x - q { q + 1. } else { q }
+            }
+        };
+    q as u16
+}
+
+/// Converts integer `q` of `bits` bits to a value in the interval
+/// _[0, 1]_.
+///
+/// Bits of `q` higher than `bits` are ignored.
+///
+/// # Panics
+///
+/// If `bits` is not in the range *[1, 16]*.
+#[inline]
+pub fn dequantize(q: u16, bits: u32) -> f32 {
+    let max = max_value(bits);
+    // division, instead of multiplying the reciprocal, is exact for
+    // quantizing back.
+    ((q as u32) & (max as u32)) as f32 / max
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ Rounding, quantize, dequantize };
+
+    #[test]
+    fn test_round_trip() {
+        for bits in 1..17 {
+            for q in 0..(1u32 << bits) {
+                let q = q as u16;
+                let v = dequantize(q, bits);
+                assert_eq!(quantize(v, bits, Rounding::Nearest), q);
+                assert_eq!(quantize(v, bits, Rounding::Floor), q);
+                assert_eq!(quantize(v, bits, Rounding::Stochastic), q);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounding() {
+        let v = 100.25 / 255.;
+        assert_eq!(quantize(v, 8, Rounding::Nearest), 100);
+        assert_eq!(quantize(100.75 / 255., 8, Rounding::Nearest), 101);
+        assert_eq!(quantize(100.75 / 255., 8, Rounding::Floor), 100);
+        assert_eq!(quantize(-1., 8, Rounding::Stochastic), 0);
+        assert_eq!(quantize(2., 16, Rounding::Stochastic), 65535);
+        let n = 10000;
+        let sum = (0..n).fold(0u32, |s, _| s + quantize(v, 8, Rounding::Stochastic) as u32);
+        let mean = sum as f32 / n as f32;
+        assert!(mean > 100.15 && mean < 100.35);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bits() {
+        quantize(0.5, 17, Rounding::Nearest);
+    }
+}
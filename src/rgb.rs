@@ -22,7 +22,8 @@
 // THE SOFTWARE.
 
 use glm::*;
-use super::space::ColorSpace;
+use super::space::{ ColorSpace, Pod3 };
+use super::quantize::{ Rounding, quantize, dequantize };
 use std::ops::{ Add, Sub, Mul };
 use std::mem;
 use rand::{ Rand, Rng, thread_rng };
@@ -38,6 +39,8 @@ pub struct Rgb {
     b: f32,
 }
 
+unsafe impl Pod3 for Rgb {}
+
 impl ColorSpace for Rgb {
     #[inline(always)]
     fn from_rgb(rgb: Rgb) -> Rgb {
@@ -47,10 +50,16 @@ impl ColorSpace for Rgb {
     fn to_rgb(&self) -> Rgb {
         *self
     }
+    #[inline]
+    fn from_rgb_slice(src: &[Rgb], dst: &mut [Rgb]) {
+        dst.copy_from_slice(src);
+    }
+    #[inline]
+    fn to_rgb_slice(src: &[Rgb], dst: &mut [Rgb]) {
+        dst.copy_from_slice(src);
+    }
 }
 
-const ONE_OVER_256: f32 = 0.0039215686274509803921568627451_f32;
-
 impl Rgb {
 
     /// Constructs a `Rgb` color from given `red`, `green` and `blue` values.
@@ -169,15 +178,29 @@ impl Rgb {
     #[inline]
     pub fn from_u8(r: u8, g: u8, b: u8) -> Rgb {
         let cv = |c: u8| -> f32 {
-            match c {
-                255 => 1f32,
-                0   => 0f32,
-                _   => (c as f32) * ONE_OVER_256,
-            }
+            dequantize(c as u16, 8)
         };
         Rgb { r: cv(r), g: cv(g), b: cv(b) }
     }
 
+    /// Converts _self_ to red, green and blue values in `u8` type, rounding
+    /// to the nearest integers.
+    ///
+    /// It is the inverse of `Rgb::from_u8()`.
+    ///
+    /// # Example
+    /// ```
+    /// use glm_color::*;
+    ///
+    /// assert_eq!(CORNFLOWER_BLUE.to_u8(), (100, 149, 237));
+    /// assert_eq!(Rgb::new(0.5, 0., 1.).to_u8(), (128, 0, 255));
+    /// ```
+    #[inline]
+    pub fn to_u8(&self) -> (u8, u8, u8) {
+        let (r, g, b) = self.quantize(8, Rounding::Nearest);
+        (r as u8, g as u8, b as u8)
+    }
+
     /// Constructs a `Rgb` value from a 32-bit unsigned integer `clr`.
     ///
     /// The lower 24 bits of `clr` are intepreted as 3 8-bit values,
@@ -199,6 +222,66 @@ impl Rgb {
         Rgb::from_u8(cv(2), cv(1), cv(0))
     }
 
+    /// Converts _self_ to a 32-bit unsigned integer.
+    ///
+    /// It is the inverse of `Rgb::from_u32()`. The higest 8 bits are `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use glm_color::*;
+    /// assert_eq!(CYAN.to_u32(), 0x00FFFF);
+    /// assert_eq!(Rgb::from_u32(0x123456).to_u32(), 0x123456);
+    /// ```
+    #[inline]
+    pub fn to_u32(&self) -> u32 {
+        let (r, g, b) = self.to_u8();
+        ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+    }
+
+    /// Quantizes _self_ to integers of `bits` bits, with rounding mode
+    /// `rounding`.
+    ///
+    /// Common choices of `bits` are `8`, `10`, `12` and `16`.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is not in the range *[1, 16]*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::quantize::Rounding;
+    ///
+    /// let clr = Rgb::new(1., 0.5, 0.);
+    /// assert_eq!(clr.quantize(10, Rounding::Nearest), (1023, 512, 0));
+    /// assert_eq!(clr.quantize(10, Rounding::Floor), (1023, 511, 0));
+    /// ```
+    #[inline]
+    pub fn quantize(&self, bits: u32, rounding: Rounding) -> (u16, u16, u16) {
+        (quantize(self.r, bits, rounding),
+         quantize(self.g, bits, rounding),
+         quantize(self.b, bits, rounding))
+    }
+
+    /// Constructs a `Rgb` value from red, green and blue integers of `bits`
+    /// bits.
+    ///
+    /// It is the inverse of `Rgb::quantize()`.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is not in the range *[1, 16]*.
+    #[inline]
+    pub fn dequantize(r: u16, g: u16, b: u16, bits: u32) -> Rgb {
+        Rgb {
+            r: dequantize(r, bits),
+            g: dequantize(g, bits),
+            b: dequantize(b, bits)
+        }
+    }
+
     /// Returns the hue of _self_. It is a value in the interval [0, 2π).
     ///
     /// # Example
@@ -276,7 +359,8 @@ impl Rgb {
     /// Returns _self_'s lunimance.
     ///
     /// Luminance is calculated with the same parameters as `YCbCr` color space's
-    /// `from_rgb()` function.
+    /// `from_rgb()` function. Because _self_ is linear, it is also the
+    /// relative luminance defined in WCAG 2.x. See the `contrast` module.
     ///
     /// # Example
     ///
@@ -436,14 +520,14 @@ pub fn grey(x: u8) -> Rgb {
 /// Color constants, derived from [SVG's color keywords](http://www.w3.org/TR/SVGColor12/#syntax).
 pub mod consts {
 
-    use super::{ Rgb, ONE_OVER_256 };
+    use super::Rgb;
 
     macro_rules! rgb {
         ($r: expr, $g: expr, $b: expr) => {
             Rgb {
-                r: ($r as f32) * ONE_OVER_256,
-                g: ($g as f32) * ONE_OVER_256,
-                b: ($b as f32) * ONE_OVER_256
+                r: ($r as f32) / 255.,
+                g: ($g as f32) / 255.,
+                b: ($b as f32) / 255.
             }
         }
     }
@@ -567,6 +651,8 @@ pub mod consts {
     pub const PLUM                  : Rgb = rgb!(221, 160, 221);
     pub const POWDER_BLUE           : Rgb = rgb!(176, 224, 230);
     pub const PURPLE                : Rgb = rgb!(128, 0, 128);
+    /// Added in CSS Color Module Level 4.
+    pub const REBECCA_PURPLE        : Rgb = rgb!(102, 51, 153);
     pub const RED                   : Rgb = rgb!(255, 0, 0);
     pub const ROSY_BROWN            : Rgb = rgb!(188, 143, 143);
     pub const ROYAL_BLUE            : Rgb = rgb!(65, 105, 225);
@@ -624,6 +710,33 @@ mod test {
         assert_eq!(RED + RED, RED);
     }
 
+    #[test]
+    fn test_u8() {
+        for c in 0..256 {
+            let c = c as u8;
+            assert_eq!(Rgb::from_u8(c, c, c).to_u8(), (c, c, c));
+            let clr = Rgb::from_u8(c, 0, 255 - c);
+            assert_eq!(clr.red(), c as f32 / 255.);
+            assert_eq!(clr.green(), 0.);
+            assert_eq!(clr.blue(), (255 - c) as f32 / 255.);
+        }
+        assert_eq!(Rgb::from_u8(255, 165, 0), ORANGE);
+        assert_eq!(Rgb::from_u8(100, 149, 237), CORNFLOWER_BLUE);
+    }
+
+    #[test]
+    fn test_u32() {
+        fn prop(x: u32) -> bool {
+            let clr = x & 0xFFFFFF;
+            Rgb::from_u32(x).to_u32() == clr &&
+            Rgb::from_u32(clr).quantize(8, Rounding::Floor) == {
+                let (r, g, b) = Rgb::from_u32(clr).to_u8();
+                (r as u16, g as u16, b as u16)
+            }
+        }
+        quickcheck(prop as fn(u32) -> bool);
+    }
+
     #[test]
     fn test_mul() {
         fn prop(clr: Rgb) -> bool {
@@ -0,0 +1,171 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+
+// Kernels for converting slices of colors.
+//
+// On x86_64, AVX2 is detected at runtime, and SSE2 is always available.
+// Other targets use the scalar loop. All paths give the same results,
+// bit for bit.
+
+use glm::*;
+use super::space::Pod3;
+use std::mem;
+use std::slice;
+
+// Re-interprets a slice of colors that have 3 `f32` channels as a slice of
+// `Vec3`.
+#[inline]
+pub fn as_vec3s<T: Pod3>(s: &[T]) -> &[Vec3] {
+    debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<Vec3>());
+    unsafe { slice::from_raw_parts(s.as_ptr() as *const Vec3, s.len()) }
+}
+
+#[inline]
+pub fn as_vec3s_mut<T: Pod3>(s: &mut [T]) -> &mut [Vec3] {
+    debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<Vec3>());
+    unsafe { slice::from_raw_parts_mut(s.as_mut_ptr() as *mut Vec3, s.len()) }
+}
+
+// Multiplies each vector in `src` by matrix `m`, clamps the results to
+// `[lo, hi]`, and writes them to `dst`.
+pub fn transform(m: &Mat3, lo: Vec3, hi: Vec3, src: &[Vec3], dst: &mut [Vec3]) {
+    assert_eq!(src.len(), dst.len());
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { x86::transform_avx2(m, lo, hi, src, dst) }
+        } else {
+            unsafe { x86::transform_sse2(m, lo, hi, src, dst) }
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    transform_scalar(m, lo, hi, src, dst)
+}
+
+#[inline]
+fn transform_scalar(m: &Mat3, lo: Vec3, hi: Vec3, src: &[Vec3], dst: &mut [Vec3]) {
+    for (s, d) in src.iter().zip(dst.iter_mut()) {
+        *d = clamp(m.mul_v(s), lo, hi);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+
+    use glm::*;
+    use std::arch::x86_64::*;
+
+    // Processes one vector a time. Loads 4 floats, so the last vector is
+    // left to the scalar loop.
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn transform_sse2(m: &Mat3, lo: Vec3, hi: Vec3, src: &[Vec3], dst: &mut [Vec3]) {
+        let n = src.len();
+        let c0 = _mm_setr_ps(m[0].x, m[0].y, m[0].z, 0.);
+        let c1 = _mm_setr_ps(m[1].x, m[1].y, m[1].z, 0.);
+        let c2 = _mm_setr_ps(m[2].x, m[2].y, m[2].z, 0.);
+        let vlo = _mm_setr_ps(lo.x, lo.y, lo.z, 0.);
+        let vhi = _mm_setr_ps(hi.x, hi.y, hi.z, 0.);
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let mut i = 0;
+        while i + 1 < n {
+            let v = _mm_loadu_ps(s.add(3 * i));
+            let x = _mm_shuffle_ps(v, v, 0x00);
+            let y = _mm_shuffle_ps(v, v, 0x55);
+            let z = _mm_shuffle_ps(v, v, 0xAA);
+            // same order of operations as `Mat3::mul_v()`.
+            let r = _mm_add_ps(_mm_add_ps(_mm_mul_ps(c0, x), _mm_mul_ps(c1, y)), _mm_mul_ps(c2, z));
+            let r = _mm_min_ps(_mm_max_ps(r, vlo), vhi);
+            _mm_store_sd(d.add(3 * i) as *mut f64, _mm_castps_pd(r));
+            _mm_store_ss(d.add(3 * i + 2), _mm_movehl_ps(r, r));
+            i += 1;
+        }
+        super::transform_scalar(m, lo, hi, &src[i..], &mut dst[i..]);
+    }
+
+    // Processes two vectors a time, one in each 128-bit lane. Loads 8
+    // floats, so the last vectors are left to the SSE2 loop.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn transform_avx2(m: &Mat3, lo: Vec3, hi: Vec3, src: &[Vec3], dst: &mut [Vec3]) {
+        let n = src.len();
+        let c0 = _mm256_setr_ps(m[0].x, m[0].y, m[0].z, 0., m[0].x, m[0].y, m[0].z, 0.);
+        let c1 = _mm256_setr_ps(m[1].x, m[1].y, m[1].z, 0., m[1].x, m[1].y, m[1].z, 0.);
+        let c2 = _mm256_setr_ps(m[2].x, m[2].y, m[2].z, 0., m[2].x, m[2].y, m[2].z, 0.);
+        let vlo = _mm256_setr_ps(lo.x, lo.y, lo.z, 0., lo.x, lo.y, lo.z, 0.);
+        let vhi = _mm256_setr_ps(hi.x, hi.y, hi.z, 0., hi.x, hi.y, hi.z, 0.);
+        // `xyzxyzxy` to `xyz_xyz_`, and back.
+        let spread = _mm256_setr_epi32(0, 1, 2, 2, 3, 4, 5, 5);
+        let pack = _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 6, 6);
+        // writes only 6 floats, which does not touch the next vector.
+        let mask = _mm256_setr_epi32(-1, -1, -1, -1, -1, -1, 0, 0);
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let mut i = 0;
+        while i + 2 < n {
+            let v = _mm256_permutevar8x32_ps(_mm256_loadu_ps(s.add(3 * i)), spread);
+            let x = _mm256_permute_ps(v, 0x00);
+            let y = _mm256_permute_ps(v, 0x55);
+            let z = _mm256_permute_ps(v, 0xAA);
+            let r = _mm256_add_ps(_mm256_add_ps(_mm256_mul_ps(c0, x), _mm256_mul_ps(c1, y)), _mm256_mul_ps(c2, z));
+            let r = _mm256_min_ps(_mm256_max_ps(r, vlo), vhi);
+            _mm256_maskstore_ps(d.add(3 * i), mask, _mm256_permutevar8x32_ps(r, pack));
+            i += 2;
+        }
+        transform_sse2(m, lo, hi, &src[i..], &mut dst[i..]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use super::{ transform, transform_scalar };
+    use rand::{ Rng, thread_rng };
+
+    #[test]
+    fn test_transform() {
+        let mut rng = thread_rng();
+        let m = mat3(
+            0.4124564, 0.2126729, 0.0193339,
+            0.3575761, 0.7151522, 0.119192,
+            0.1804375, 0.0721750, 0.9503041
+        );
+        let (lo, hi) = (vec3(0., -0.1, 0.2), vec3(1., 0.5, 0.7));
+        for n in 0..40 {
+            let src: Vec<Vec3> = (0..n).map(|_| {
+                vec3(rng.gen(), rng.gen(), rng.gen())
+            }).collect();
+            let mut expected = vec![vec3(0., 0., 0.); n];
+            let mut dst = vec![vec3(0., 0., 0.); n];
+            transform_scalar(&m, lo, hi, &src, &mut expected);
+            transform(&m, lo, hi, &src, &mut dst);
+            assert_eq!(dst, expected);
+            #[cfg(target_arch = "x86_64")]
+            {
+                let mut dst = vec![vec3(0., 0., 0.); n];
+                unsafe { super::x86::transform_sse2(&m, lo, hi, &src, &mut dst) };
+                assert_eq!(dst, expected);
+            }
+        }
+    }
+}
@@ -22,6 +22,8 @@
 // THE SOFTWARE.
 
 use super::rgb::Rgb;
+use std::mem;
+use std::slice;
 
 /// `ColorSpace` is the representation and interpretation of color values.
 ///
@@ -34,8 +36,54 @@ pub trait ColorSpace {
 
     /// Converts _self_ to a color value in RGB color space.
     fn to_rgb(&self) -> Rgb;
+
+    /// Converts each color value in `src` from RGB color space, and writes
+    /// the results to `dst`.
+    ///
+    /// The default implementation calls `from_rgb()` for each color.
+    /// Color spaces that are linear transformations of RGB override it with
+    /// SIMD code.
+    ///
+    /// # Panics
+    ///
+    /// If `src` and `dst` have different lengths.
+    fn from_rgb_slice(src: &[Rgb], dst: &mut [Self]) where Self: Sized {
+        assert_eq!(src.len(), dst.len());
+        for (s, d) in src.iter().zip(dst.iter_mut()) {
+            *d = Self::from_rgb(*s);
+        }
+    }
+
+    /// Converts each color value in `src` to RGB color space, and writes
+    /// the results to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// If `src` and `dst` have different lengths.
+    fn to_rgb_slice(src: &[Self], dst: &mut [Rgb]) where Self: Sized {
+        assert_eq!(src.len(), dst.len());
+        for (s, d) in src.iter().zip(dst.iter_mut()) {
+            *d = s.to_rgb();
+        }
+    }
 }
 
+/// Color types that are `#[repr(C)]` structs of three `f32` channels.
+///
+/// This trait is not exported, so it can only be implemented by the color
+/// types of this crate.
+///
+/// # Safety
+///
+/// The implementing type must have no padding, and every bit pattern must be
+/// a valid value of it, so a slice of one can be re-interpreted as a slice of
+/// another.
+pub unsafe trait Pod3: Copy {}
+
+// Number of colors converted a time by `convert_slice()` and
+// `convert_in_place()`, through a buffer on the stack.
+const CHUNK_LEN: usize = 256;
+
 /// Converts `clr` in linear RGB space to color space `T`.
 #[inline]
 pub fn from_rgb<T: ColorSpace>(clr: Rgb) -> T {
@@ -47,3 +95,67 @@ pub fn from_rgb<T: ColorSpace>(clr: Rgb) -> T {
 pub fn to_rgb<T: ColorSpace>(clr: &T) -> Rgb {
     clr.to_rgb()
 }
+
+/// Converts each color value in `src` from color space `S` to color space
+/// `D`, and writes the results to `dst`.
+///
+/// # Panics
+///
+/// If `src` and `dst` have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let src = [RED, GREEN, BLUE];
+/// let mut dst = [Srgb::new(0., 0., 0.); 3];
+/// convert_slice(&src, &mut dst);
+/// assert_eq!(dst[2], from_rgb::<Srgb>(BLUE));
+/// ```
+pub fn convert_slice<S: ColorSpace, D: ColorSpace>(src: &[S], dst: &mut [D]) {
+    assert_eq!(src.len(), dst.len());
+    let mut buf = [Rgb::new(0., 0., 0.); CHUNK_LEN];
+    for (s, d) in src.chunks(CHUNK_LEN).zip(dst.chunks_mut(CHUNK_LEN)) {
+        let tmp = &mut buf[..s.len()];
+        S::to_rgb_slice(s, tmp);
+        D::from_rgb_slice(tmp, d);
+    }
+}
+
+/// Converts each color value in `buf` from color space `S` to color space
+/// `D` in place, and returns `buf` re-interpreted as a slice of `D`.
+///
+/// Only color types that have three `f32` channels, e.g., `Rgb`, `Srgb`,
+/// `Xyz` or `Lab`, can be converted in place.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let mut buf = [RED, GREEN, BLUE];
+/// {
+///     let srgb: &mut [Srgb] = convert_in_place(&mut buf);
+///     assert_eq!(srgb[0], from_rgb::<Srgb>(RED));
+/// }
+/// ```
+pub fn convert_in_place<S, D>(buf: &mut [S]) -> &mut [D]
+    where S: ColorSpace + Pod3, D: ColorSpace + Pod3
+{
+    debug_assert_eq!(mem::size_of::<S>(), mem::size_of::<D>());
+    debug_assert_eq!(mem::align_of::<S>(), mem::align_of::<D>());
+    let mut tmp = [Rgb::new(0., 0., 0.); CHUNK_LEN];
+    for chunk in buf.chunks_mut(CHUNK_LEN) {
+        let n = chunk.len();
+        S::to_rgb_slice(chunk, &mut tmp[..n]);
+        // the values of `S` in `chunk` are not used from now on. If
+        // `from_rgb_slice()` panics, `buf` is left with a mix of `S` and `D`
+        // values, which are both valid `S` values.
+        let dst = unsafe {
+            slice::from_raw_parts_mut(chunk.as_mut_ptr() as *mut D, n)
+        };
+        D::from_rgb_slice(&tmp[..n], dst);
+    }
+    unsafe { slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut D, buf.len()) }
+}
@@ -23,7 +23,7 @@
 
 use glm::*;
 use glm::ext::recip;
-use super::space::{ ColorSpace, from_rgb };
+use super::space::{ ColorSpace, Pod3, from_rgb };
 use super::rgb::Rgb;
 use std::mem;
 use rand::{ Rand, Rng };
@@ -34,6 +34,7 @@ use rand::{ Rand, Rng };
 ///
 /// Wikipedia page [sRGB](http://en.wikipedia.org/wiki/SRGB), from where the
 /// numbers used in conversion between linear RGB and sRGB are got.
+#[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Srgb {
     r: f32,
@@ -92,45 +93,253 @@ impl ApproxEq for Srgb {
     }
 }
 
+// Encodes a linear channel value.
+#[inline]
+fn encode(f: f32) -> f32 {
+    // numbers from Wikipedia.
+    if f <= 0.0031308 {
+        f * 12.92
+    } else {
+        let a = 0.055_f32;
+        (1. + a) * pow(f, recip(2.4_f32)) - a
+    }
+}
+
+// Decodes an sRGB channel value.
+#[inline]
+fn decode(f: f32) -> f32 {
+    if f <= 0.04045 {
+        f / 12.92
+    } else {
+        let a = 0.055_f32;
+        pow((f + a) * recip(1. + a), 2.4)
+    }
+}
+
+unsafe impl Pod3 for Srgb {}
+
 impl ColorSpace for Srgb {
     #[inline]
     fn from_rgb(rgb: Rgb) -> Srgb {
-        let v3 = rgb.as_vec3().map(|f| {
-            // numbers from Wikipedia.
-            if f <= 0.0031308 {
-                f * 12.92
-            } else {
-                let a = 0.055_f32;
-                (1. + a) * pow(f, recip(2.4_f32)) - a
-            }
-        });
+        let v3 = rgb.as_vec3().map(encode);
         Srgb { r: v3.x, g: v3.y, b: v3.z }
     }
     #[inline]
     fn to_rgb(&self) -> Rgb {
-        let v3 = self.as_vec3().map(|f| {
-            if f <= 0.04045 {
-                f / 12.92
-            } else {
-                let a = 0.055_f32;
-                pow((f + a) * recip(1. + a), 2.4)
-            }
-        });
+        let v3 = self.as_vec3().map(decode);
         Rgb::new(v3.x, v3.y, v3.z)
     }
 }
 
+/// Decodes an 8-bit sRGB channel value `c` to linear.
+///
+/// This function looks up a table, and the result is exactly the same as
+/// the red channel of `Srgb::new(c / 255, 0, 0).to_rgb()`.
+#[inline]
+pub fn decode_u8(c: u8) -> f32 {
+    DECODE_U8[c as usize]
+}
+
+/// Encodes linear channel value `f` to an 8-bit sRGB value.
+///
+/// The result is exactly the same as quantizing the red channel of
+/// `from_rgb::<Srgb>(Rgb::new(f, 0, 0))` to 8 bits, rounding to the nearest
+/// integer, but no `pow` is called.
+///
+/// `NaN` is encoded to `0`.
+#[inline]
+pub fn encode_u8(f: f32) -> u8 {
+    // the encoded value increases with `f`. So `f` is encoded to the number
+    // of thresholds that are less than or equal to `f`. Buckets indexed by
+    // the exponent and the highest 4 bits of mantissa tell where to begin
+    // counting, and at most 5 thresholds are compared.
+    if f.is_nan() || f < ENCODE_U8_MIN {
+        return 0;
+    }
+    if f >= 1. {
+        return 255;
+    }
+    let b = ((f.to_bits() - ENCODE_U8_MIN.to_bits()) >> 19) as usize;
+    let mut c = ENCODE_U8_BUCKETS[b] as usize;
+    while c < 255 && f >= ENCODE_U8_THRESHOLDS[c] {
+        c += 1;
+    }
+    c as u8
+}
+
+impl Rgb {
+    /// Constructs a `Rgb` value from 8-bit sRGB values `r`, `g` and `b`.
+    ///
+    /// It is a fast version of `Srgb::new(r / 255, g / 255, b / 255).to_rgb()`,
+    /// and the results are the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let srgb = Srgb::new(1., 128. / 255., 51. / 255.);
+    /// assert_eq!(Rgb::from_srgb_u8(255, 128, 51), srgb.to_rgb());
+    /// ```
+    #[inline]
+    pub fn from_srgb_u8(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb::new(decode_u8(r), decode_u8(g), decode_u8(b))
+    }
+
+    /// Converts _self_ to 8-bit sRGB values.
+    ///
+    /// It is a fast version of converting _self_ to `Srgb` and rounding
+    /// each channel to the nearest 8-bit integer, and the results are the
+    /// same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// assert_eq!(Rgb::from_srgb_u8(255, 128, 51).to_srgb_u8(), (255, 128, 51));
+    /// ```
+    #[inline]
+    pub fn to_srgb_u8(&self) -> (u8, u8, u8) {
+        let v = self.as_vec3();
+        (encode_u8(v.x), encode_u8(v.y), encode_u8(v.z))
+    }
+}
+
 /// Equivalent to `Srgb::new()`.
 pub fn srgb(r: f32, g: f32, b: f32) -> Srgb {
     Srgb::new(r, g, b)
 }
 
+// The tables below are generated from `decode()` and `encode()`, and are
+// checked in the tests.
+
+// `decode(c / 255)` for each 8-bit value `c`.
+static DECODE_U8: [f32; 256] = [
+    0.0, 0.000303527, 0.000607054, 0.000910581, 0.001214108, 0.001517635,
+    0.001821162, 0.0021246888, 0.002428216, 0.002731743, 0.00303527,
+    0.0033465356, 0.003676507, 0.004024717, 0.004391442, 0.0047769533,
+    0.005181516, 0.0056053917, 0.0060488326, 0.00651209, 0.00699541,
+    0.0074990317, 0.008023192, 0.008568125, 0.009134057, 0.009721218,
+    0.010329823, 0.010960094, 0.011612245, 0.012286487, 0.012983031,
+    0.013702081, 0.014443841, 0.015208514, 0.015996292, 0.016807375,
+    0.017641952, 0.018500218, 0.019382358, 0.020288559, 0.02121901,
+    0.022173883, 0.023153365, 0.02415763, 0.025186857, 0.026241219,
+    0.027320888, 0.028426038, 0.029556833, 0.03071344, 0.03189603, 0.033104762,
+    0.0343398, 0.03560131, 0.036889445, 0.038204364, 0.039546225, 0.0409152,
+    0.04231141, 0.043735027, 0.045186203, 0.046665084, 0.048171822,
+    0.049706563, 0.051269468, 0.052860655, 0.05448028, 0.056128494,
+    0.057805434, 0.05951124, 0.061246056, 0.06301002, 0.06480328, 0.06662595,
+    0.06847818, 0.07036011, 0.07227186, 0.07421358, 0.07618539, 0.07818743,
+    0.08021983, 0.082282715, 0.084376216, 0.086500466, 0.08865559, 0.09084171,
+    0.09305898, 0.095307484, 0.09758736, 0.09989874, 0.10224175, 0.10461649,
+    0.10702311, 0.10946172, 0.111932434, 0.11443538, 0.11697067, 0.119538434,
+    0.122138776, 0.12477182, 0.1274377, 0.13013649, 0.13286833, 0.13563335,
+    0.13843162, 0.1412633, 0.14412849, 0.14702728, 0.1499598, 0.15292616,
+    0.15592647, 0.15896083, 0.16202937, 0.1651322, 0.1682694, 0.1714411,
+    0.17464739, 0.17788841, 0.18116423, 0.18447499, 0.18782076, 0.19120167,
+    0.19461781, 0.1980693, 0.20155624, 0.2050787, 0.20863685, 0.21223073,
+    0.21586053, 0.21952623, 0.22322798, 0.22696589, 0.23074007, 0.2345506,
+    0.23839758, 0.24228114, 0.2462014, 0.25015837, 0.25415218, 0.2581829,
+    0.26225072, 0.26635566, 0.27049786, 0.27467737, 0.27889434, 0.2831488,
+    0.2874409, 0.2917707, 0.29613832, 0.30054384, 0.30498737, 0.30946895,
+    0.31398875, 0.31854683, 0.32314324, 0.32777813, 0.33245158, 0.33716366,
+    0.34191445, 0.3467041, 0.35153264, 0.35640016, 0.3613068, 0.3662526,
+    0.37123778, 0.37626222, 0.3813261, 0.38642952, 0.39157256, 0.3967553,
+    0.40197787, 0.4072403, 0.4125427, 0.41788515, 0.42326775, 0.42869055,
+    0.4341537, 0.43965724, 0.44520125, 0.45078585, 0.45641106, 0.46207705,
+    0.46778384, 0.47353154, 0.47932023, 0.48514998, 0.4910209, 0.49693304,
+    0.5028865, 0.50888133, 0.5149177, 0.5209956, 0.5271152, 0.5332765,
+    0.5394796, 0.5457246, 0.5520115, 0.5583405, 0.56471163, 0.5711249,
+    0.5775805, 0.5840785, 0.5906189, 0.5972019, 0.6038274, 0.6104956,
+    0.61720663, 0.62396044, 0.6307572, 0.63759696, 0.64447975, 0.6514057,
+    0.65837485, 0.66538733, 0.6724432, 0.67954254, 0.6866853, 0.6938718,
+    0.7011019, 0.7083758, 0.71569353, 0.72305524, 0.7304609, 0.73791057,
+    0.74540436, 0.7529423, 0.76052463, 0.7681513, 0.77582234, 0.7835379,
+    0.79129803, 0.79910284, 0.80695236, 0.8148467, 0.82278585, 0.83076996,
+    0.8387991, 0.8468733, 0.8549927, 0.8631573, 0.8713672, 0.87962234,
+    0.8879232, 0.89626926, 0.90466124, 0.9130986, 0.9215819, 0.9301109,
+    0.9386858, 0.9473066, 0.9559735, 0.9646863, 0.9734455, 0.9822506,
+    0.9911022, 1.0,
+];
+
+// `2^-13`, the lower bound of the first bucket. Values below it are
+// encoded to `0`.
+const ENCODE_U8_MIN: f32 = 0.00012207031;
+
+// The smallest value that is encoded to `c + 1`, for each 8-bit value `c`
+// other than `255`.
+static ENCODE_U8_THRESHOLDS: [f32; 255] = [
+    0.0001517635, 0.0004552905, 0.0007588175, 0.0010623444, 0.0013658715,
+    0.0016693984, 0.0019729254, 0.0022764525, 0.0025799794, 0.0028835065,
+    0.003188301, 0.0035092593, 0.0038483152, 0.0042057484, 0.004581833,
+    0.004976838, 0.0053910245, 0.0058246506, 0.006277969, 0.006751227,
+    0.007244668, 0.00775853, 0.008293048, 0.008848452, 0.009424971,
+    0.010022826, 0.010642237, 0.011283421, 0.011946592, 0.012631958,
+    0.01333973, 0.014070112, 0.014823305, 0.015599506, 0.016398912,
+    0.017221719, 0.018068116, 0.018938296, 0.019832447, 0.020750748,
+    0.021693386, 0.02266054, 0.023652392, 0.024669116, 0.025710888,
+    0.026777888, 0.027870275, 0.028988224, 0.030131904, 0.031301484,
+    0.03249713, 0.033718996, 0.034967247, 0.036242045, 0.037543554,
+    0.038871925, 0.04022733, 0.0416099, 0.043019794, 0.04445717, 0.045922183,
+    0.047414973, 0.048935693, 0.05048449, 0.052061513, 0.0536669, 0.055300806,
+    0.05696336, 0.058654718, 0.060375027, 0.062124398, 0.06390299, 0.06571093,
+    0.067548364, 0.06941543, 0.07131225, 0.07323897, 0.075195715, 0.07718262,
+    0.07919982, 0.08124745, 0.083325624, 0.08543449, 0.08757418, 0.089744784,
+    0.09194645, 0.09417932, 0.09644349, 0.0987391, 0.101066284, 0.103425145,
+    0.10581581, 0.10823841, 0.11069305, 0.11317987, 0.11569897, 0.11825048,
+    0.120834544, 0.12345122, 0.12610066, 0.12878297, 0.13149828, 0.13424669,
+    0.13702832, 0.13984329, 0.1426917, 0.14557368, 0.14848931, 0.15143874,
+    0.15442206, 0.1574394, 0.16049086, 0.16357651, 0.16669649, 0.16985093,
+    0.17303991, 0.17626356, 0.179522, 0.18281527, 0.18614352, 0.18950683,
+    0.19290534, 0.19633915, 0.19980834, 0.20331304, 0.20685333, 0.21042933,
+    0.21404113, 0.21768883, 0.22137254, 0.22509237, 0.22884846, 0.23264079,
+    0.23646954, 0.2403348, 0.24423666, 0.24817523, 0.2521506, 0.25616288,
+    0.26021215, 0.2642985, 0.26842207, 0.2725829, 0.2767811, 0.28101683,
+    0.2852901, 0.28960103, 0.29394972, 0.2983363, 0.3027608, 0.30722335,
+    0.31172404, 0.31626296, 0.32084018, 0.3254558, 0.33010998, 0.33480272,
+    0.33953413, 0.34430435, 0.3491135, 0.35396153, 0.3588486, 0.36377484,
+    0.36874026, 0.37374502, 0.37878916, 0.3838728, 0.38899603, 0.3941589,
+    0.39936155, 0.40460402, 0.40988642, 0.41520885, 0.42057136, 0.42597404,
+    0.43141702, 0.43690035, 0.44242412, 0.4479884, 0.4535933, 0.4592389,
+    0.46492526, 0.4706525, 0.47642067, 0.4822299, 0.4880802, 0.49397182,
+    0.4999046, 0.50587875, 0.51189435, 0.5179515, 0.5240502, 0.5301906,
+    0.5363728, 0.54259676, 0.5488627, 0.55517066, 0.5615207, 0.56791294,
+    0.5743474, 0.58082414, 0.58734334, 0.59390503, 0.6005092, 0.6071561,
+    0.6138457, 0.6205781, 0.62735337, 0.6341716, 0.6410329, 0.64793724,
+    0.6548848, 0.6618756, 0.6689098, 0.6759875, 0.6831085, 0.69027317,
+    0.69748145, 0.70473343, 0.7120292, 0.7193689, 0.72675246, 0.7341801,
+    0.74165183, 0.74916774, 0.7567279, 0.7643323, 0.7719812, 0.7796745,
+    0.7874123, 0.79519475, 0.8030219, 0.81089383, 0.8188105, 0.8267722,
+    0.8347788, 0.8428305, 0.85092723, 0.8590692, 0.86725646, 0.87548906,
+    0.8837672, 0.8920905, 0.90045965, 0.90887415, 0.9173346, 0.92584056,
+    0.93439263, 0.9429903, 0.9516342, 0.9603239, 0.96906006, 0.97784203,
+    0.98667055, 0.99554527,
+];
+
+// The encoded value of the lower bound of each bucket.
+static ENCODE_U8_BUCKETS: [u8; 208] = [
+    0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 3,
+    4, 4, 4, 4, 4, 5, 5, 5, 5, 5, 6, 6, 6, 6, 6, 7, 7, 8, 8, 8, 9, 9, 10, 10,
+    10, 11, 11, 12, 12, 12, 13, 13, 14, 15, 15, 16, 16, 17, 18, 18, 19, 19, 20,
+    20, 21, 21, 22, 23, 23, 24, 25, 26, 27, 27, 28, 29, 30, 30, 31, 32, 32, 33,
+    34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 49, 51, 53,
+    54, 56, 57, 58, 60, 61, 62, 64, 65, 66, 67, 68, 70, 71, 73, 75, 77, 79, 81,
+    83, 85, 86, 88, 90, 91, 93, 95, 96, 98, 99, 102, 105, 107, 110, 113, 115,
+    118, 120, 122, 124, 127, 129, 131, 133, 135, 137, 141, 145, 148, 152, 155,
+    158, 162, 165, 168, 171, 174, 177, 179, 182, 185, 188, 193, 198, 202, 207,
+    212, 216, 220, 225, 229, 233, 237, 240, 244, 248, 251,
+];
+
 #[cfg(test)]
 mod test {
     use glm::*;
-    use space::ColorSpace;
+    use space::{ ColorSpace, from_rgb };
     use rgb::Rgb;
-    use super::Srgb;
+    use quantize::{ Rounding, quantize, dequantize };
+    use super::{ Srgb, encode, decode, decode_u8, encode_u8 };
+    use super::{ ENCODE_U8_MIN, ENCODE_U8_THRESHOLDS };
     use quickcheck::*;
 
     #[test]
@@ -142,4 +351,57 @@ mod test {
         }
         quickcheck(prop as fn(Rgb) -> bool);
     }
+
+    // 8-bit encoding as defined by `Srgb`.
+    fn encode_u8_ref(f: f32) -> u8 {
+        quantize(encode(f), 8, Rounding::Nearest) as u8
+    }
+
+    #[test]
+    fn test_decode_u8() {
+        for c in 0..256 {
+            let f = dequantize(c, 8);
+            assert_eq!(decode_u8(c as u8), decode(f));
+            assert_eq!(Rgb::from_srgb_u8(c as u8, 0, 0), Srgb::new(f, 0., 0.).to_rgb());
+        }
+    }
+
+    #[test]
+    fn test_encode_u8() {
+        // the boundaries of codes.
+        for (c, &t) in ENCODE_U8_THRESHOLDS.iter().enumerate() {
+            let prev = f32::from_bits(t.to_bits() - 1);
+            assert_eq!(encode_u8_ref(t), c as u8 + 1);
+            assert_eq!(encode_u8_ref(prev), c as u8);
+            assert_eq!(encode_u8(t), c as u8 + 1);
+            assert_eq!(encode_u8(prev), c as u8);
+        }
+        // the boundaries of buckets.
+        let mut bits = ENCODE_U8_MIN.to_bits();
+        while bits < 1f32.to_bits() {
+            for &b in [bits - 1, bits].iter() {
+                let f = f32::from_bits(b);
+                assert_eq!(encode_u8(f), encode_u8_ref(f));
+            }
+            bits += 1 << 19;
+        }
+        assert_eq!(encode_u8(-1.), 0);
+        assert_eq!(encode_u8(2.), 255);
+        assert_eq!(encode_u8(f32::NAN), 0);
+    }
+
+    #[test]
+    fn test_to_srgb_u8() {
+        fn prop(clr: Rgb) -> bool {
+            let v = from_rgb::<Srgb>(clr);
+            let (r, g, b) = clr.to_srgb_u8();
+            (r as u16, g as u16, b as u16) == (
+                quantize(v.red(), 8, Rounding::Nearest),
+                quantize(v.green(), 8, Rounding::Nearest),
+                quantize(v.blue(), 8, Rounding::Nearest)
+            ) &&
+            Rgb::from_srgb_u8(r, g, b).to_srgb_u8() == (r, g, b)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
 }
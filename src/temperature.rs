@@ -0,0 +1,365 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Color temperature.
+//!
+//! Colors of blackbody radiators are computed by integrating Planck's law
+//! against the CIE 1931 2° standard observer, tabulated at 5 nm intervals
+//! from 380 nm to 780 nm. The correlated color temperature (CCT) and the
+//! distance from the Planckian locus (Duv) of a color are estimated with
+//! Ohno's combined method.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glm_color::*;
+//!
+//! // a warm light for a lighting editor.
+//! let warm = Rgb::from_temperature(2700.);
+//! assert_eq!(warm.red(), 1.);
+//! assert!(warm.blue() < warm.green());
+//!
+//! let (cct, duv) = warm.cct();
+//! assert!((cct - 2700.).abs() < 20. && duv.abs() < 0.002);
+//! ```
+//!
+//! # See
+//!
+//! - Yoshi Ohno, _Practical Use and Calculation of CCT and Duv_, LEUKOS,
+//!   2014.
+//! - Wikipedia page [Planckian locus](http://en.wikipedia.org/wiki/Planckian_locus).
+
+use glm::*;
+use super::rgb::Rgb;
+use super::xyz::{ Xyz, xyz_to_rgb };
+use super::space::from_rgb;
+
+/// The lowest temperature, in kelvins, that is handled.
+pub const MIN_TEMPERATURE: f32 = 1000.;
+
+/// The highest temperature, in kelvins, that is handled.
+pub const MAX_TEMPERATURE: f32 = 100000.;
+
+// The second radiation constant, in meter kelvins, as used by CIE.
+const C2: f64 = 1.4388e-2;
+
+// CIE 1931 2° color matching functions, from 380 nm to 780 nm, with the
+// digits of the CIE table.
+#[allow(clippy::excessive_precision)]
+static CIE_1931: [[f32; 3]; 81] = [
+    [0.001368, 0.000039, 0.006450], // 380
+    [0.002236, 0.000064, 0.010550], // 385
+    [0.004243, 0.000120, 0.020050], // 390
+    [0.007650, 0.000217, 0.036210], // 395
+    [0.014310, 0.000396, 0.067850], // 400
+    [0.023190, 0.000640, 0.110200], // 405
+    [0.043510, 0.001210, 0.207400], // 410
+    [0.077630, 0.002180, 0.371300], // 415
+    [0.134380, 0.004000, 0.645600], // 420
+    [0.214770, 0.007300, 1.039050], // 425
+    [0.283900, 0.011600, 1.385600], // 430
+    [0.328500, 0.016840, 1.622960], // 435
+    [0.348280, 0.023000, 1.747060], // 440
+    [0.348060, 0.029800, 1.782600], // 445
+    [0.336200, 0.038000, 1.772110], // 450
+    [0.318700, 0.048000, 1.744100], // 455
+    [0.290800, 0.060000, 1.669200], // 460
+    [0.251100, 0.073900, 1.528100], // 465
+    [0.195360, 0.090980, 1.287640], // 470
+    [0.142100, 0.112600, 1.041900], // 475
+    [0.095640, 0.139020, 0.812950], // 480
+    [0.057950, 0.169300, 0.616200], // 485
+    [0.032010, 0.208020, 0.465180], // 490
+    [0.014700, 0.258600, 0.353300], // 495
+    [0.004900, 0.323000, 0.272000], // 500
+    [0.002400, 0.407300, 0.212300], // 505
+    [0.009300, 0.503000, 0.158200], // 510
+    [0.029100, 0.608200, 0.111700], // 515
+    [0.063270, 0.710000, 0.078250], // 520
+    [0.109600, 0.793200, 0.057250], // 525
+    [0.165500, 0.862000, 0.042160], // 530
+    [0.225750, 0.914850, 0.029840], // 535
+    [0.290400, 0.954000, 0.020300], // 540
+    [0.359700, 0.980300, 0.013400], // 545
+    [0.433450, 0.994950, 0.008750], // 550
+    [0.512050, 1.000000, 0.005750], // 555
+    [0.594500, 0.995000, 0.003900], // 560
+    [0.678400, 0.978600, 0.002750], // 565
+    [0.762100, 0.952000, 0.002100], // 570
+    [0.842500, 0.915400, 0.001800], // 575
+    [0.916300, 0.870000, 0.001650], // 580
+    [0.978600, 0.816300, 0.001400], // 585
+    [1.026300, 0.757000, 0.001100], // 590
+    [1.056700, 0.694900, 0.001000], // 595
+    [1.062200, 0.631000, 0.000800], // 600
+    [1.045600, 0.566800, 0.000600], // 605
+    [1.002600, 0.503000, 0.000340], // 610
+    [0.938400, 0.441200, 0.000240], // 615
+    [0.854450, 0.381000, 0.000190], // 620
+    [0.751400, 0.321000, 0.000100], // 625
+    [0.642400, 0.265000, 0.000050], // 630
+    [0.541900, 0.217000, 0.000030], // 635
+    [0.447900, 0.175000, 0.000020], // 640
+    [0.360800, 0.138200, 0.000010], // 645
+    [0.283500, 0.107000, 0.000000], // 650
+    [0.218700, 0.081600, 0.000000], // 655
+    [0.164900, 0.061000, 0.000000], // 660
+    [0.121200, 0.044580, 0.000000], // 665
+    [0.087400, 0.032000, 0.000000], // 670
+    [0.063600, 0.023200, 0.000000], // 675
+    [0.046770, 0.017000, 0.000000], // 680
+    [0.032900, 0.011920, 0.000000], // 685
+    [0.022700, 0.008210, 0.000000], // 690
+    [0.015840, 0.005723, 0.000000], // 695
+    [0.011359, 0.004102, 0.000000], // 700
+    [0.008111, 0.002929, 0.000000], // 705
+    [0.005790, 0.002091, 0.000000], // 710
+    [0.004109, 0.001484, 0.000000], // 715
+    [0.002899, 0.001047, 0.000000], // 720
+    [0.002049, 0.000740, 0.000000], // 725
+    [0.001440, 0.000520, 0.000000], // 730
+    [0.001000, 0.000361, 0.000000], // 735
+    [0.000690, 0.000249, 0.000000], // 740
+    [0.000476, 0.000172, 0.000000], // 745
+    [0.000332, 0.000120, 0.000000], // 750
+    [0.000235, 0.000085, 0.000000], // 755
+    [0.000166, 0.000060, 0.000000], // 760
+    [0.000117, 0.000042, 0.000000], // 765
+    [0.000083, 0.000030, 0.000000], // 770
+    [0.000059, 0.000021, 0.000000], // 775
+    [0.000042, 0.000015, 0.000000], // 780
+];
+
+// XYZ values of the blackbody radiator of temperature `t`, not normalized.
+fn blackbody(t: f64) -> DVec3 {
+    CIE_1931.iter().enumerate().fold(dvec3(0., 0., 0.), |sum, (i, cmf)| {
+        let l = (380 + 5 * i) as f64 * 1e-9;
+        let m = 1. / (l.powi(5) * ((C2 / (l * t)).exp() - 1.));
+        sum + dvec3(cmf[0] as f64, cmf[1] as f64, cmf[2] as f64) * m
+    })
+}
+
+// CIE 1960 UCS coordinates of `xyz`.
+#[inline]
+fn uv(xyz: DVec3) -> DVec2 {
+    let d = xyz.x + 15. * xyz.y + 3. * xyz.z;
+    dvec2(4. * xyz.x / d, 6. * xyz.y / d)
+}
+
+#[inline]
+fn clamp_temperature(kelvin: f32) -> f64 {
+    clamp(kelvin, MIN_TEMPERATURE, MAX_TEMPERATURE) as f64
+}
+
+/// Returns the chromaticity coordinates `(x, y)` of the blackbody radiator
+/// of temperature `kelvin`.
+///
+/// Parameter `kelvin` is clampped to the range
+/// *[`MIN_TEMPERATURE`, `MAX_TEMPERATURE`]*.
+#[inline]
+pub fn planckian(kelvin: f32) -> Vec2 {
+    let v = blackbody(clamp_temperature(kelvin));
+    let s = v.x + v.y + v.z;
+    vec2((v.x / s) as f32, (v.y / s) as f32)
+}
+
+// Estimates the CCT and Duv of CIE 1960 coordinates `p`.
+fn ohno(p: DVec2) -> (f64, f64) {
+    const N: usize = 15;
+    let (mut lo, mut hi) = (MIN_TEMPERATURE as f64, MAX_TEMPERATURE as f64);
+    let mut ts = [0.; N];
+    let mut ps = [dvec2(0., 0.); N];
+    let mut ds = [0.; N];
+    // cascade the table to the neighborhood of the closest temperature.
+    let m = loop {
+        let r = (hi / lo).powf(1. / (N - 1) as f64);
+        for i in 0..N {
+            ts[i] = lo * r.powi(i as i32);
+            ps[i] = uv(blackbody(ts[i]));
+            ds[i] = distance(p, ps[i]);
+        }
+        let mut m = 1;
+        for i in 1..(N - 1) {
+            if ds[i] < ds[m] {
+                m = i;
+            }
+        }
+        if r < 1.001 {
+            break m;
+        }
+        lo = ts[m - 1];
+        hi = ts[m + 1];
+    };
+    let (t0, t1, t2) = (ts[m - 1], ts[m], ts[m + 1]);
+    let (d0, d1, d2) = (ds[m - 1], ds[m], ds[m + 1]);
+    // the triangular solution.
+    let l = distance(ps[m - 1], ps[m + 1]);
+    let x = (d0 * d0 - d2 * d2 + l * l) / (2. * l);
+    let t = t0 + (t2 - t0) * x / l;
+    let vx = ps[m - 1].y + (ps[m + 1].y - ps[m - 1].y) * x / l;
+    let duv = sqrt(max(d0 * d0 - x * x, 0.)) * sign(p.y - vx);
+    if abs(duv) < 0.002 {
+        (t, duv)
+    } else {
+        // the parabolic solution, relative to `t1` for accuracy.
+        let (h0, h2) = (t0 - t1, t2 - t1);
+        let (e0, e2) = (d0 - d1, d2 - d1);
+        let x = h0 * h2 * (h0 - h2);
+        let a = (e0 * h2 - e2 * h0) / x;
+        let b = (e2 * h0 * h0 - e0 * h2 * h2) / x;
+        let t = t1 - b / (2. * a);
+        let d = d1 - b * b / (4. * a);
+        let vt = uv(blackbody(t)).y;
+        (t, d * sign(p.y - vt))
+    }
+}
+
+impl Xyz {
+    /// Constructs the `Xyz` value of the blackbody radiator of temperature
+    /// `kelvin`, whose luminance is `1`.
+    ///
+    /// Parameter `kelvin` is clampped to the range
+    /// *[`MIN_TEMPERATURE`, `MAX_TEMPERATURE`]*.
+    #[inline]
+    pub fn from_temperature(kelvin: f32) -> Xyz {
+        let v = blackbody(clamp_temperature(kelvin));
+        Xyz::new((v.x / v.y) as f32, 1., (v.z / v.y) as f32)
+    }
+
+    /// Estimates the correlated color temperature, in kelvins, and the Duv
+    /// of _self_.
+    ///
+    /// Duv is the distance from the Planckian locus in the CIE 1960 UCS
+    /// diagram. It is positive if _self_ is above the locus, i.e., greenish,
+    /// and negative if _self_ is below the locus, i.e., pinkish. CCT is
+    /// meaningful only if Duv is small, e.g., less than `0.05`.
+    ///
+    /// The temperature is in the range
+    /// *[`MIN_TEMPERATURE`, `MAX_TEMPERATURE`]*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::illuminant::{ Illuminant, Observer };
+    ///
+    /// let d65 = Illuminant::D65.xyz(Observer::Cie1931);
+    /// let (cct, duv) = d65.cct();
+    /// assert!((cct - 6504.).abs() < 2.);
+    /// assert!((duv - 0.0032).abs() < 0.0001);
+    /// ```
+    pub fn cct(&self) -> (f32, f32) {
+        let (t, duv) = ohno(uv(dvec3(self.x() as f64, self.y() as f64, self.z() as f64)));
+        (clamp(t as f32, MIN_TEMPERATURE, MAX_TEMPERATURE), duv as f32)
+    }
+}
+
+impl Rgb {
+    /// Constructs the color of the blackbody radiator of temperature
+    /// `kelvin`.
+    ///
+    /// The color is normalized so that the maximum channel is `1`. The
+    /// radiators of low temperatures are out of the RGB gamut, and are
+    /// clampped.
+    ///
+    /// Parameter `kelvin` is clampped to the range
+    /// *[`MIN_TEMPERATURE`, `MAX_TEMPERATURE`]*.
+    pub fn from_temperature(kelvin: f32) -> Rgb {
+        let xyz = Xyz::from_temperature(kelvin);
+        let v = max_s(xyz_to_rgb() * *xyz.as_vec3(), 0.);
+        let m = v.max();
+        Rgb::new(v.x / m, v.y / m, v.z / m)
+    }
+
+    /// Estimates the correlated color temperature, in kelvins, and the Duv
+    /// of _self_.
+    ///
+    /// See `Xyz::cct()` for details.
+    #[inline]
+    pub fn cct(&self) -> (f32, f32) {
+        from_rgb::<Xyz>(*self).cct()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use xyz::Xyz;
+    use rgb::Rgb;
+    use illuminant::{ Illuminant, Observer };
+    use super::{ CIE_1931, planckian, blackbody, uv, ohno };
+    use quickcheck::*;
+
+    #[test]
+    fn test_planckian() {
+        // values from Wikipedia. Illuminant A is defined with a slightly
+        // different second radiation constant.
+        assert!(planckian(1000.).is_close_to(&vec2(0.6527, 0.3445), 0.0001));
+        assert!(planckian(6500.).is_close_to(&vec2(0.3135, 0.3237), 0.0001));
+        let a = Illuminant::A.chromaticity(Observer::Cie1931);
+        assert!(planckian(2856.).is_close_to(&a, 0.0001));
+        // equal energy.
+        let e = CIE_1931.iter().fold(vec3(0., 0., 0.), |s, c| s + vec3(c[0], c[1], c[2]));
+        assert!(is_close_to(&e.x, &e.y, 0.001) && is_close_to(&e.z, &e.y, 0.001));
+    }
+
+    #[test]
+    fn test_cct() {
+        fn prop(t: f32) -> bool {
+            let kelvin = 1000. + t.fract().abs() * 39000.;
+            let (cct, duv) = Xyz::from_temperature(kelvin).cct();
+            abs(cct - kelvin) < kelvin * 0.00005 && abs(duv) < 0.00001
+        }
+        quickcheck(prop as fn(f32) -> bool);
+    }
+
+    #[test]
+    fn test_duv() {
+        // points that are off the locus, along the normal of the locus.
+        for &t in [2000., 3000., 4500., 6500., 10000., 20000.].iter() {
+            for &d in [-0.03, -0.01, 0.005, 0.02].iter() {
+                let p = uv(blackbody(t));
+                let q = uv(blackbody(t * 1.0001));
+                let tangent = normalize(q - p);
+                let o = p + dvec2(tangent.y, -tangent.x) * d;
+                // some of the points are not real colors, so `Xyz` can not be used.
+                let (cct, duv) = ohno(o);
+                assert!(abs(cct - t) < t * 0.0005, "{} {} {}", t, d, cct);
+                assert!(abs(duv - d) < 0.0001, "{} {} {}", t, d, duv);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_temperature() {
+        let warm = Rgb::from_temperature(1000.);
+        assert_eq!(warm.red(), 1.);
+        assert_eq!(warm.blue(), 0.);
+        let cold = Rgb::from_temperature(40000.);
+        assert_eq!(cold.blue(), 1.);
+        assert!(cold.red() < cold.green());
+        // about white.
+        let d65 = Rgb::from_temperature(6504.);
+        assert!(d65.is_close_to(&Rgb::new(1., 1., 1.), 0.1));
+    }
+}
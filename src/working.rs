@@ -0,0 +1,405 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! RGB working spaces other than sRGB.
+//!
+//! Each color space is defined by its primaries, its white point and its
+//! transfer function. Values are converted to and from linear `Rgb` through
+//! CIE XYZ. The Bradford transform is used if the white point is not D65.
+//! The matrices are derived from the constants in `primaries`, and are
+//! combined once, when first used.
+//!
+//! # Note
+//!
+//! All colors in the `Rgb` gamut are in the gamut of these color spaces.
+//! The reverse is not true, and out of gamut colors are clampped when
+//! converted to `Rgb`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate glm;
+//! # extern crate glm_color;
+//! # fn main() {
+//! use glm::*;
+//! use glm_color::*;
+//!
+//! let p3: DisplayP3 = from_rgb(RED);
+//! assert!(p3.red() < 1. && p3.green() > 0.);
+//! assert!(p3.to_rgb().is_close_to(&RED, 0.0001));
+//!
+//! let white: AcesCg = from_rgb(WHITE);
+//! assert!(white.is_close_to(&AcesCg::new(1., 1., 1.), 0.0001));
+//! # }
+//! ```
+
+use glm::*;
+use super::space::{ ColorSpace, Pod3, from_rgb };
+use super::rgb::Rgb;
+use super::srgb::Srgb;
+use super::xyz::{ Xyz, D65, rgb_to_xyz, xyz_to_rgb };
+use super::adapt::Cat;
+use super::illuminant::D65_XY;
+use super::primaries::*;
+use std::f32;
+use std::mem;
+use std::sync::OnceLock;
+use rand::{ Rand, Rng };
+
+macro_rules! working_space(
+    (
+        $(#[$attr: meta])*
+        struct $t: ident;
+        fn $ctor: ident;
+        max: $max: expr,
+        primaries: $p: expr,
+        encode: $encode: expr,
+        decode: $decode: expr
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        #[derive(Copy, Clone, PartialEq, Debug)]
+        pub struct $t {
+            r: f32,
+            g: f32,
+            b: f32
+        }
+
+        impl $t {
+            /// Creates a value from encoded channel values.
+            ///
+            /// Parameters are clampped to range *[0, max]*, where `max` is
+            /// `1` unless stated otherwise in the type's documentation.
+            #[inline]
+            pub fn new(r: f32, g: f32, b: f32) -> $t {
+                let v3 = clamp_s(vec3(r, g, b), 0., $max);
+                $t { r: v3.x, g: v3.y, b: v3.z }
+            }
+
+            /// Returns the value of red channel of _self_.
+            #[inline]
+            pub fn red(&self) -> f32 {
+                self.r
+            }
+
+            /// Returns the value of green channel of _self_.
+            #[inline]
+            pub fn green(&self) -> f32 {
+                self.g
+            }
+
+            /// Returns the value of blue channel of _self_.
+            #[inline]
+            pub fn blue(&self) -> f32 {
+                self.b
+            }
+
+            /// Re-interprets a reference of the value to `Vec3`.
+            #[inline]
+            pub fn as_vec3(&self) -> &Vec3 {
+                let v: &Vec3 = unsafe { mem::transmute(self) };
+                v
+            }
+        }
+
+        impl Rand for $t {
+            #[inline]
+            fn rand<R: Rng>(rng: &mut R) -> $t {
+                // only colors in the RGB gamut.
+                let rgb = rng.gen::<Rgb>();
+                from_rgb(rgb)
+            }
+        }
+
+        impl Eq for $t {}
+
+        impl ApproxEq for $t {
+            type BaseType = f32;
+            #[inline]
+            fn is_close_to(&self, other: &$t, max_diff: f32) -> bool {
+                self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+            }
+        }
+
+        impl $t {
+            // The matrices from and to linear `Rgb`.
+            #[inline]
+            fn matrices() -> &'static (Mat3, Mat3) {
+                static M: OnceLock<(Mat3, Mat3)> = OnceLock::new();
+                M.get_or_init(|| matrices(&$p))
+            }
+        }
+
+        unsafe impl Pod3 for $t {}
+
+        impl ColorSpace for $t {
+            #[inline]
+            fn from_rgb(rgb: Rgb) -> $t {
+                let v = $t::matrices().0.mul_v(rgb.as_vec3());
+                let e = $encode(clamp_s(v, 0., $max));
+                $t::new(e.x, e.y, e.z)
+            }
+            #[inline]
+            fn to_rgb(&self) -> Rgb {
+                let v = $t::matrices().1.mul_v(&$decode(*self.as_vec3()));
+                Rgb::new(v.x, v.y, v.z)
+            }
+        }
+
+        /// Equivalent to `new()` of the type.
+        #[inline]
+        pub fn $ctor(r: f32, g: f32, b: f32) -> $t {
+            $t::new(r, g, b)
+        }
+    }
+);
+
+// The matrices that convert linear `Rgb` to and from the linear values of an
+// RGB color space of primaries `p`.
+fn matrices(p: &Primaries) -> (Mat3, Mat3) {
+    if p.white() == D65_XY {
+        (p.xyz_to_rgb() * rgb_to_xyz(), xyz_to_rgb() * p.rgb_to_xyz())
+    } else {
+        let w = p.white_xyz();
+        let white = Xyz::new(w.x, w.y, w.z);
+        let from = p.xyz_to_rgb() * Cat::Bradford.matrix(D65, white) * rgb_to_xyz();
+        let to = xyz_to_rgb() * Cat::Bradford.matrix(white, D65) * p.rgb_to_xyz();
+        (from, to)
+    }
+}
+
+// The transfer function of sRGB.
+#[inline]
+fn srgb_encode(v: Vec3) -> Vec3 {
+    *from_rgb::<Srgb>(Rgb::new(v.x, v.y, v.z)).as_vec3()
+}
+
+#[inline]
+fn srgb_decode(v: Vec3) -> Vec3 {
+    *Srgb::new(v.x, v.y, v.z).to_rgb().as_vec3()
+}
+
+// Adobe RGB (1998) uses a pure gamma of `563/256`.
+#[inline]
+fn adobe_encode(v: Vec3) -> Vec3 {
+    pow(v, vec3(256. / 563., 256. / 563., 256. / 563.))
+}
+
+#[inline]
+fn adobe_decode(v: Vec3) -> Vec3 {
+    pow(v, vec3(563. / 256., 563. / 256., 563. / 256.))
+}
+
+// The OETF of ITU-R BT.2020, with the exact values of the constants.
+const REC2020_ALPHA: f32 = 1.0992968;
+const REC2020_BETA: f32 = 0.0180540;
+
+#[inline]
+fn rec2020_encode(v: Vec3) -> Vec3 {
+    v.map(|f| {
+        if f < REC2020_BETA {
+            f * 4.5
+        } else {
+            REC2020_ALPHA * pow(f, 0.45) - (REC2020_ALPHA - 1.)
+        }
+    })
+}
+
+#[inline]
+fn rec2020_decode(v: Vec3) -> Vec3 {
+    v.map(|f| {
+        if f < REC2020_BETA * 4.5 {
+            f / 4.5
+        } else {
+            pow((f + REC2020_ALPHA - 1.) / REC2020_ALPHA, 1. / 0.45)
+        }
+    })
+}
+
+// ROMM RGB, gamma `1.8` with a linear segment near black.
+#[inline]
+fn prophoto_encode(v: Vec3) -> Vec3 {
+    v.map(|f| if f < 1. / 512. { f * 16. } else { pow(f, 1. / 1.8) })
+}
+
+#[inline]
+fn prophoto_decode(v: Vec3) -> Vec3 {
+    v.map(|f| if f < 16. / 512. { f / 16. } else { pow(f, 1.8) })
+}
+
+#[inline]
+fn linear(v: Vec3) -> Vec3 {
+    v
+}
+
+working_space! {
+    /// The Display P3 color space.
+    ///
+    /// It has the primaries of DCI-P3, the D65 white point and the transfer
+    /// function of sRGB.
+    ///
+    /// # See
+    ///
+    /// - [CSS Color Module Level 4](http://www.w3.org/TR/css-color-4/#predefined-display-p3).
+    struct DisplayP3;
+    fn display_p3;
+    max: 1.,
+    primaries: DISPLAY_P3,
+    encode: srgb_encode,
+    decode: srgb_decode
+}
+
+working_space! {
+    /// The Adobe RGB (1998) color space.
+    ///
+    /// It has the D65 white point, and a pure gamma of `563/256`, which is
+    /// about `2.2`.
+    ///
+    /// # See
+    ///
+    /// - [Adobe RGB (1998) Color Image Encoding](https://www.adobe.com/digitalimag/adobergb.html).
+    struct AdobeRgb;
+    fn adobe_rgb;
+    max: 1.,
+    primaries: ADOBE_RGB,
+    encode: adobe_encode,
+    decode: adobe_decode
+}
+
+working_space! {
+    /// The ITU-R BT.2020 color space.
+    ///
+    /// It has the D65 white point, and the transfer function defined in the
+    /// recommendation.
+    ///
+    /// # See
+    ///
+    /// - [ITU-R BT.2020](https://www.itu.int/rec/R-REC-BT.2020).
+    struct Rec2020;
+    fn rec2020;
+    max: 1.,
+    primaries: REC2020,
+    encode: rec2020_encode,
+    decode: rec2020_decode
+}
+
+working_space! {
+    /// The ProPhoto RGB (ROMM RGB) color space.
+    ///
+    /// It has the D50 white point, and a gamma of `1.8` with a linear segment
+    /// near black.
+    ///
+    /// # See
+    ///
+    /// - [ProPhoto RGB color space](http://en.wikipedia.org/wiki/ProPhoto_RGB_color_space).
+    struct ProPhotoRgb;
+    fn prophoto_rgb;
+    max: 1.,
+    primaries: PROPHOTO_RGB,
+    encode: prophoto_encode,
+    decode: prophoto_decode
+}
+
+working_space! {
+    /// The ACEScg color space.
+    ///
+    /// It has the AP1 primaries and the ACES white point, which is close to
+    /// D60. It is linear, and channel values are not bounded above.
+    ///
+    /// # See
+    ///
+    /// - [ACEScg specification, S-2014-004](https://www.oscars.org/science-technology/aces/aces-documentation).
+    struct AcesCg;
+    fn acescg;
+    max: f32::INFINITY,
+    primaries: ACES_AP1,
+    encode: linear,
+    decode: linear
+}
+
+working_space! {
+    /// The ACES2065-1 color space.
+    ///
+    /// It has the AP0 primaries and the ACES white point, which is close to
+    /// D60. It is linear, and channel values are not bounded above.
+    ///
+    /// # See
+    ///
+    /// - [ACES2065-1 specification, SMPTE ST 2065-1](https://www.oscars.org/science-technology/aces/aces-documentation).
+    struct Aces2065;
+    fn aces2065;
+    max: f32::INFINITY,
+    primaries: ACES_AP0,
+    encode: linear,
+    decode: linear
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use space::{ ColorSpace, from_rgb };
+    use rgb::Rgb;
+    use rgb::consts::*;
+    use super::*;
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            from_rgb::<DisplayP3>(clr).to_rgb().is_close_to(&clr, 0.0001) &&
+            from_rgb::<AdobeRgb>(clr).to_rgb().is_close_to(&clr, 0.0001) &&
+            from_rgb::<Rec2020>(clr).to_rgb().is_close_to(&clr, 0.0001) &&
+            from_rgb::<ProPhotoRgb>(clr).to_rgb().is_close_to(&clr, 0.0001) &&
+            from_rgb::<AcesCg>(clr).to_rgb().is_close_to(&clr, 0.0001) &&
+            from_rgb::<Aces2065>(clr).to_rgb().is_close_to(&clr, 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_white() {
+        let one = vec3(1., 1., 1.);
+        assert!(from_rgb::<DisplayP3>(WHITE).as_vec3().is_close_to(&one, 0.00001));
+        assert!(from_rgb::<AdobeRgb>(WHITE).as_vec3().is_close_to(&one, 0.00001));
+        assert!(from_rgb::<Rec2020>(WHITE).as_vec3().is_close_to(&one, 0.00001));
+        assert!(from_rgb::<ProPhotoRgb>(WHITE).as_vec3().is_close_to(&one, 0.00001));
+        assert!(from_rgb::<AcesCg>(WHITE).as_vec3().is_close_to(&one, 0.00001));
+        assert!(from_rgb::<Aces2065>(WHITE).as_vec3().is_close_to(&one, 0.00001));
+    }
+
+    #[test]
+    fn test_primaries() {
+        // sRGB primaries in wider gamuts.
+        let p3 = from_rgb::<DisplayP3>(RED);
+        assert!(p3.is_close_to(&DisplayP3::new(0.9175, 0.2003, 0.1386), 0.0001));
+        let rec2020 = from_rgb::<Rec2020>(GREEN);
+        assert!(rec2020.is_close_to(&Rec2020::new(0.5675, 0.9593, 0.2690), 0.0001));
+        let cg = from_rgb::<AcesCg>(BLUE);
+        assert!(cg.is_close_to(&AcesCg::new(0.0474, 0.0135, 0.8698), 0.0001));
+        let ap0 = from_rgb::<Aces2065>(RED);
+        assert!(ap0.is_close_to(&Aces2065::new(0.4396, 0.0898, 0.0175), 0.0001));
+        // HDR values are kept.
+        assert_eq!(AcesCg::new(2., -1., 0.5).as_vec3(), &vec3(2., 0., 0.5));
+        assert_eq!(AdobeRgb::new(2., -1., 0.5).as_vec3(), &vec3(1., 0., 0.5));
+    }
+}
@@ -0,0 +1,362 @@
+//
+// GLM-COLOR
+//
+// Copyright (c) 2015 The glm-color authors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+use glm::*;
+use super::space::{ ColorSpace, Pod3, from_rgb };
+use super::rgb::Rgb;
+use super::simd;
+use super::illuminant::{ D65_XY, D50_XY };
+use super::primaries::SRGB;
+use std::f32;
+use std::mem;
+use std::sync::OnceLock;
+use rand::{ Rand, Rng };
+
+/// CIE XYZ tristimulus values of the D65 reference white, i.e., of
+/// `illuminant::D65_XY`, normalized so that `Y` is `1`.
+pub const D65: Xyz = white(D65_XY);
+
+/// CIE XYZ tristimulus values of the D50 reference white, i.e., of
+/// `illuminant::D50_XY`, normalized so that `Y` is `1`.
+pub const D50: Xyz = white(D50_XY);
+
+// CIE XYZ of white point `xy`, with `Y` being `1`.
+const fn white(xy: Vec2) -> Xyz {
+    Xyz { x: xy.x / xy.y, y: 1., z: (1. - xy.x - xy.y) / xy.y }
+}
+
+/// The CIE 1931 XYZ color space.
+///
+/// Linear RGB is assumed to have the sRGB (ITU-R BT.709) primaries and the
+/// D65 white point. `Y` is normalized so that `WHITE` has luminance `1`.
+///
+/// # See
+///
+/// - Wikipedia page [CIE 1931 color space](http://en.wikipedia.org/wiki/CIE_1931_color_space).
+/// - `primaries::SRGB`, from where the conversion matrices are derived.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let white: Xyz = from_rgb(WHITE);
+/// assert!((white.y() - 1.).abs() < 0.00001);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Xyz {
+    x: f32,
+    y: f32,
+    z: f32
+}
+
+impl Xyz {
+    /// Constructs a `Xyz` value.
+    ///
+    /// Negative parameters are clampped to `0`.
+    #[inline]
+    pub fn new(x: f32, y: f32, z: f32) -> Xyz {
+        let v = max_s(vec3(x, y, z), 0.);
+        Xyz { x: v.x, y: v.y, z: v.z }
+    }
+
+    /// Returns the `X` component of _self_.
+    #[inline]
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    /// Returns the `Y` component, i.e., the luminance, of _self_.
+    #[inline]
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    /// Returns the `Z` component of _self_.
+    #[inline]
+    pub fn z(&self) -> f32 {
+        self.z
+    }
+
+    /// Returns the chromaticity coordinates `(x, y)` of _self_.
+    ///
+    /// For black, which has no chromaticity, the chromaticity of D65 is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    ///
+    /// let white: Xyz = from_rgb(WHITE);
+    /// let xy = white.chromaticity();
+    /// assert!((xy.x - 0.3127).abs() < 0.0001);
+    /// assert!((xy.y - 0.3290).abs() < 0.0001);
+    /// ```
+    #[inline]
+    pub fn chromaticity(&self) -> Vec2 {
+        let sum = self.as_vec3().sum();
+        if is_approx_eq(&sum, &0.) {
+            D65.chromaticity()
+        } else {
+            vec2(self.x / sum, self.y / sum)
+        }
+    }
+
+    /// Converts _self_ to the CIE xyY color space.
+    #[inline]
+    pub fn to_xyy(&self) -> Xyy {
+        let xy = self.chromaticity();
+        Xyy { x: xy.x, y: xy.y, lum: self.y }
+    }
+
+    /// Re-interprets a reference of `Xyz` to `Vec3`.
+    #[inline]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let v: &Vec3 = unsafe { mem::transmute(self) };
+        v
+    }
+}
+
+// sRGB primaries, D65. Derived once.
+#[inline]
+pub(crate) fn rgb_to_xyz() -> Mat3 {
+    static M: OnceLock<Mat3> = OnceLock::new();
+    *M.get_or_init(|| SRGB.rgb_to_xyz())
+}
+
+#[inline]
+pub(crate) fn xyz_to_rgb() -> Mat3 {
+    static M: OnceLock<Mat3> = OnceLock::new();
+    *M.get_or_init(|| SRGB.xyz_to_rgb())
+}
+
+impl Eq for Xyz {}
+
+impl ApproxEq for Xyz {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Xyz, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+impl Rand for Xyz {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Xyz {
+        // only colors in the RGB gamut.
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+unsafe impl Pod3 for Xyz {}
+
+impl ColorSpace for Xyz {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Xyz {
+        let v = rgb_to_xyz().mul_v(rgb.as_vec3());
+        Xyz { x: v.x, y: v.y, z: v.z }
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        let v = xyz_to_rgb().mul_v(self.as_vec3());
+        Rgb::new(v.x, v.y, v.z)
+    }
+    fn from_rgb_slice(src: &[Rgb], dst: &mut [Xyz]) {
+        let inf = vec3(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        simd::transform(
+            &rgb_to_xyz(), -inf, inf,
+            simd::as_vec3s(src), simd::as_vec3s_mut(dst)
+        )
+    }
+    fn to_rgb_slice(src: &[Xyz], dst: &mut [Rgb]) {
+        simd::transform(
+            &xyz_to_rgb(), vec3(0., 0., 0.), vec3(1., 1., 1.),
+            simd::as_vec3s(src), simd::as_vec3s_mut(dst)
+        )
+    }
+}
+
+/// Equivalent to `Xyz::new()`.
+#[inline]
+pub fn xyz(x: f32, y: f32, z: f32) -> Xyz {
+    Xyz::new(x, y, z)
+}
+
+/// The CIE xyY color space.
+///
+/// The chromaticity coordinates `x` and `y` specify the chromaticity, and
+/// `Y` specifies the luminance, which is the same as the `Y` of `Xyz`.
+///
+/// # Example
+///
+/// ```rust
+/// use glm_color::*;
+///
+/// let red: Xyy = from_rgb(RED);
+/// assert!((red.x() - 0.64).abs() < 0.0001);
+/// assert!((red.y() - 0.33).abs() < 0.0001);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Xyy {
+    x: f32,
+    y: f32,
+    lum: f32
+}
+
+impl Xyy {
+    /// Constructs a `Xyy` value.
+    ///
+    /// Parameters `x` and `y` are clampped to the range *[0, 1]*, and
+    /// negative `lum` is clampped to `0`.
+    #[inline]
+    pub fn new(x: f32, y: f32, lum: f32) -> Xyy {
+        let xy = clamp_s(vec2(x, y), 0., 1.);
+        Xyy { x: xy.x, y: xy.y, lum: max(lum, 0.) }
+    }
+
+    /// Returns the chromaticity coordinate `x` of _self_.
+    #[inline]
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    /// Returns the chromaticity coordinate `y` of _self_.
+    #[inline]
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    /// Returns the luminance `Y` of _self_.
+    #[inline]
+    pub fn luminance(&self) -> f32 {
+        self.lum
+    }
+
+    /// Returns the chromaticity coordinates `(x, y)` of _self_.
+    #[inline]
+    pub fn chromaticity(&self) -> Vec2 {
+        vec2(self.x, self.y)
+    }
+
+    /// Converts _self_ to the CIE XYZ color space.
+    #[inline]
+    pub fn to_xyz(&self) -> Xyz {
+        if is_approx_eq(&self.y, &0.) {
+            Xyz { x: 0., y: 0., z: 0. }
+        } else {
+            let r = self.lum / self.y;
+            Xyz::new(self.x * r, self.lum, (1. - self.x - self.y) * r)
+        }
+    }
+
+    /// Re-interprets a reference of `Xyy` to `Vec3`.
+    #[inline]
+    pub fn as_vec3(&self) -> &Vec3 {
+        let v: &Vec3 = unsafe { mem::transmute(self) };
+        v
+    }
+}
+
+impl Eq for Xyy {}
+
+impl ApproxEq for Xyy {
+    type BaseType = f32;
+    #[inline]
+    fn is_close_to(&self, other: &Xyy, max_diff: f32) -> bool {
+        self.as_vec3().is_close_to(other.as_vec3(), max_diff)
+    }
+}
+
+impl Rand for Xyy {
+    #[inline]
+    fn rand<R: Rng>(rng: &mut R) -> Xyy {
+        let rgb = rng.gen::<Rgb>();
+        from_rgb(rgb)
+    }
+}
+
+unsafe impl Pod3 for Xyy {}
+
+impl ColorSpace for Xyy {
+    #[inline]
+    fn from_rgb(rgb: Rgb) -> Xyy {
+        Xyz::from_rgb(rgb).to_xyy()
+    }
+    #[inline]
+    fn to_rgb(&self) -> Rgb {
+        self.to_xyz().to_rgb()
+    }
+}
+
+/// Equivalent to `Xyy::new()`.
+#[inline]
+pub fn xyy(x: f32, y: f32, lum: f32) -> Xyy {
+    Xyy::new(x, y, lum)
+}
+
+#[cfg(test)]
+mod test {
+    use glm::*;
+    use space::ColorSpace;
+    use rgb::Rgb;
+    use rgb::consts::BLACK;
+    use super::{ Xyz, Xyy };
+    use quickcheck::*;
+
+    #[test]
+    fn test_to_rgb() {
+        fn prop(clr: Rgb) -> bool {
+            let xyz: Xyz = ColorSpace::from_rgb(clr);
+            let xyy: Xyy = ColorSpace::from_rgb(clr);
+            is_close_to(&xyz.to_rgb(), &clr, 0.0001) &&
+            is_close_to(&xyy.to_rgb(), &clr, 0.0001) &&
+            is_close_to(&xyz.y(), &clr.lunimance(), 0.0001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_black() {
+        let xyy: Xyy = ColorSpace::from_rgb(BLACK);
+        assert_eq!(xyy.luminance(), 0.);
+        assert_eq!(xyy.to_rgb(), BLACK);
+    }
+
+    #[test]
+    fn test_slice() {
+        fn prop(src: Vec<Rgb>) -> bool {
+            let mut xyz = vec![Xyz::new(0., 0., 0.); src.len()];
+            let mut rgb = vec![BLACK; src.len()];
+            Xyz::from_rgb_slice(&src, &mut xyz);
+            Xyz::to_rgb_slice(&xyz, &mut rgb);
+            src.iter().zip(xyz.iter().zip(rgb.iter())).all(|(&c, (x, r))| {
+                *x == Xyz::from_rgb(c) && *r == x.to_rgb()
+            })
+        }
+        quickcheck(prop as fn(Vec<Rgb>) -> bool);
+    }
+}
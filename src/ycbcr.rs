@@ -22,13 +22,103 @@
 // THE SOFTWARE.
 
 use glm::*;
-use super::space::ColorSpace;
+use super::space::{ ColorSpace, Pod3 };
 use super::rgb::Rgb;
+use super::simd;
 use std::mem;
 use rand::{ Rand, Rng };
 
+/// Standards that define the luma coefficients used in conversion between
+/// RGB and YCbCr.
+///
+/// # See
+///
+/// Wikipedia page [YCbCr](http://en.wikipedia.org/wiki/YCbCr), from where
+/// the coefficients are got.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Standard {
+    /// ITU-R BT.601, used by SDTV.
+    Bt601,
+    /// ITU-R BT.709, used by HDTV.
+    Bt709,
+    /// ITU-R BT.2020, used by UHDTV.
+    Bt2020,
+    /// SMPTE 240M, used by the early HDTV.
+    Smpte240m,
+}
+
+impl Standard {
+    /// Returns the luma coefficients `(Kr, Kb)` of _self_.
+    ///
+    /// The coefficient of green is `1 - Kr - Kb`.
+    #[inline]
+    pub fn coefficients(&self) -> (f32, f32) {
+        match *self {
+            Standard::Bt601 => (0.299, 0.114),
+            Standard::Bt709 => (0.2126, 0.0722),
+            Standard::Bt2020 => (0.2627, 0.0593),
+            Standard::Smpte240m => (0.212, 0.087),
+        }
+    }
+
+    /// Returns the matrix that converts linear RGB values to YCbCr.
+    #[inline]
+    pub fn matrix(&self) -> Mat3 {
+        let (kr, kb) = self.coefficients();
+        let kg = 1. - kr - kb;
+        let cb = 0.5 / (1. - kb);
+        let cr = 0.5 / (1. - kr);
+        // column major.
+        mat3(
+            kr, -kr * cb,  0.5,
+            kg, -kg * cb, -kg * cr,
+            kb,  0.5,     -kb * cr
+        )
+    }
+
+    /// Returns the matrix that converts YCbCr values to linear RGB.
+    ///
+    /// The matrix is derived from the coefficients of _self_, so it is the
+    /// exact inverse of `matrix()`.
+    #[inline]
+    pub fn inverse_matrix(&self) -> Mat3 {
+        let (kr, kb) = self.coefficients();
+        let kg = 1. - kr - kb;
+        let r = 2. * (1. - kr);
+        let b = 2. * (1. - kb);
+        mat3(
+            1.,  1.,             1.,
+            0., -kb * b / kg,    b,
+            r,  -kr * r / kg,    0.
+        )
+    }
+}
+
+impl Default for Standard {
+    #[inline]
+    fn default() -> Standard {
+        Standard::Bt709
+    }
+}
+
+/// Ranges of quantized YCbCr values.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Range {
+    /// The full range. `Y'` is mapped to *[0, 2^n - 1]*, and `Cb` and `Cr`
+    /// are mapped to *[1, 2^n - 1]*, where `n` is the bit depth.
+    Full,
+    /// The limited range, a.k.a. studio swing. At 8 bits, `Y'` is mapped to
+    /// *[16, 235]*, and `Cb` and `Cr` are mapped to *[16, 240]*. Values are
+    /// scaled by `2^(n - 8)` for bit depth `n`.
+    Limited,
+}
+
 /// The YCbCr color model.
 ///
+/// The `ColorSpace` implementation uses the coefficients defined in ITU-R
+/// BT.709. Use `from_rgb_with_standard()` and `to_rgb_with_standard()` for
+/// other standards.
+///
 /// # See
 ///
 /// Wikipedia page [YCbCr](http://en.wikipedia.org/wiki/YCbCr).
@@ -39,8 +129,9 @@ use rand::{ Rand, Rng };
 /// use glm_color::*;
 ///
 /// let brown_yuv: YCbCr = ColorSpace::from_rgb(BROWN);
-/// assert_eq!(brown_yuv.y(), BROWN.lunimance());
+/// assert!((brown_yuv.y() - BROWN.lunimance()).abs() < 0.000001);
 /// ```
+#[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct YCbCr {
     y:  f32,
@@ -60,6 +151,105 @@ impl YCbCr {
         YCbCr { y: lum, cb: uv.x, cr: uv.y }
     }
 
+    /// Converts `rgb` to YCbCr using the coefficients defined in `standard`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::ycbcr::Standard;
+    ///
+    /// let sd = YCbCr::from_rgb_with_standard(RED, Standard::Bt601);
+    /// assert_eq!(sd.y(), 0.299);
+    /// assert_eq!(sd.cr(), 0.5);
+    /// ```
+    #[inline]
+    pub fn from_rgb_with_standard(rgb: Rgb, standard: Standard) -> YCbCr {
+        let v = standard.matrix().mul_v(rgb.as_vec3());
+        YCbCr::new(v.x, v.y, v.z)
+    }
+
+    /// Converts _self_ to linear RGB using the coefficients defined in
+    /// `standard`.
+    #[inline]
+    pub fn to_rgb_with_standard(&self, standard: Standard) -> Rgb {
+        let v = standard.inverse_matrix().mul_v(self.as_vec3());
+        Rgb::new(v.x, v.y, v.z)
+    }
+
+    /// Quantizes _self_ to integers of `bits` bits in `range`.
+    ///
+    /// Values are rounded to the nearest integers and clipped to
+    /// *[0, 2^bits - 1]*.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is not in the range *[8, 16]*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::ycbcr::Range;
+    ///
+    /// let white: YCbCr = from_rgb(WHITE);
+    /// assert_eq!(white.quantize(8, Range::Limited), (235, 128, 128));
+    /// assert_eq!(white.quantize(10, Range::Full), (1023, 512, 512));
+    /// ```
+    pub fn quantize(&self, bits: u32, range: Range) -> (u16, u16, u16) {
+        assert!((8..=16).contains(&bits), "bit depth must be in [8, 16].");
+        let max = ((1u32 << bits) - 1) as f32;
+        let q = |f: f32| -> u16 {
+            clamp(round(f), 0., max) as u16
+        };
+        match range {
+            Range::Full => {
+                let half = (1u32 << (bits - 1)) as f32;
+                (q(self.y * max), q(self.cb * max + half), q(self.cr * max + half))
+            }
+            Range::Limited => {
+                let scale = (1u32 << (bits - 8)) as f32;
+                let c = |f: f32| -> u16 { q((224. * f + 128.) * scale) };
+                (q((219. * self.y + 16.) * scale), c(self.cb), c(self.cr))
+            }
+        }
+    }
+
+    /// Constructs a `YCbCr` value from integers of `bits` bits in `range`.
+    ///
+    /// Values out of the nominal range (e.g., footroom and headroom of the
+    /// limited range) are clampped.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is not in the range *[8, 16]*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use glm_color::*;
+    /// use glm_color::ycbcr::Range;
+    ///
+    /// let black = YCbCr::dequantize(16, 128, 128, 8, Range::Limited);
+    /// assert_eq!(black, YCbCr::new(0., 0., 0.));
+    /// ```
+    pub fn dequantize(y: u16, cb: u16, cr: u16, bits: u32, range: Range) -> YCbCr {
+        assert!((8..=16).contains(&bits), "bit depth must be in [8, 16].");
+        match range {
+            Range::Full => {
+                let max = ((1u32 << bits) - 1) as f32;
+                let half = (1u32 << (bits - 1)) as f32;
+                let c = |d: u16| -> f32 { (d as f32 - half) / max };
+                YCbCr::new(y as f32 / max, c(cb), c(cr))
+            }
+            Range::Limited => {
+                let scale = (1u32 << (bits - 8)) as f32;
+                let c = |d: u16| -> f32 { (d as f32 / scale - 128.) / 224. };
+                YCbCr::new((y as f32 / scale - 16.) / 219., c(cb), c(cr))
+            }
+        }
+    }
+
     /// Re-interprets a reference of `YCbCr` to `Vec3`.
     pub fn as_vec3(&self) -> &Vec3 {
         let v: &Vec3 = unsafe { mem::transmute(self) };
@@ -101,6 +291,8 @@ impl Rand for YCbCr {
     }
 }
 
+unsafe impl Pod3 for YCbCr {}
+
 impl ColorSpace for YCbCr {
     /// # Note
     ///
@@ -108,27 +300,23 @@ impl ColorSpace for YCbCr {
     /// are used.
     #[inline]
     fn from_rgb(rgb: Rgb) -> YCbCr {
-        // column major.
-        // ITU.BT-709
-        let clr_mat = mat3(
-            0.2126, -0.1146,  0.5,
-            0.7152, -0.3854, -0.4542,
-            0.0722,  0.5,    -0.0458
-        );
-        let v = clr_mat.mul_v(rgb.as_vec3());
-        YCbCr { y: v.x, cb: v.y, cr: v.z }
+        YCbCr::from_rgb_with_standard(rgb, Standard::Bt709)
     }
     #[inline]
     fn to_rgb(&self) -> Rgb {
-        // not exactly the inverse of color matrix in `from_rgb`,
-        // so the rounding errors of conversion back and forth are large.
-        let clr_mat = mat3(
-            1.,      1.,     1.,
-            0.,     -0.1873, 1.8556,
-            1.5748, -0.4682, 0.
-        );
-        let v = clr_mat.mul_v(self.as_vec3());
-        Rgb::new(v.x, v.y, v.z)
+        self.to_rgb_with_standard(Standard::Bt709)
+    }
+    fn from_rgb_slice(src: &[Rgb], dst: &mut [YCbCr]) {
+        simd::transform(
+            &Standard::Bt709.matrix(), vec3(0., -0.5, -0.5), vec3(1., 0.5, 0.5),
+            simd::as_vec3s(src), simd::as_vec3s_mut(dst)
+        )
+    }
+    fn to_rgb_slice(src: &[YCbCr], dst: &mut [Rgb]) {
+        simd::transform(
+            &Standard::Bt709.inverse_matrix(), vec3(0., 0., 0.), vec3(1., 1., 1.),
+            simd::as_vec3s(src), simd::as_vec3s_mut(dst)
+        )
     }
 }
 
@@ -143,7 +331,7 @@ mod test {
     use glm::*;
     use space::ColorSpace;
     use rgb::Rgb;
-    use super::YCbCr;
+    use super::{ YCbCr, Standard, Range };
     use quickcheck::*;
 
     #[test]
@@ -153,8 +341,71 @@ mod test {
             let rgb = yuv.to_rgb();
             abs(yuv.cb) <= 0.5 &&
             abs(yuv.cr) <= 0.5 &&
-            is_close_to(&rgb, &clr, 0.0001)
+            is_close_to(&rgb, &clr, 0.00001)
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_standards() {
+        fn prop(clr: Rgb) -> bool {
+            [Standard::Bt601, Standard::Bt709, Standard::Bt2020, Standard::Smpte240m].iter().all(|&s| {
+                let yuv = YCbCr::from_rgb_with_standard(clr, s);
+                let m = s.matrix().mul_m(&s.inverse_matrix());
+                yuv.to_rgb_with_standard(s).is_close_to(&clr, 0.00001) &&
+                m.is_close_to(&mat3(1., 0., 0., 0., 1., 0., 0., 0., 1.), 0.000001)
+            })
+        }
+        quickcheck(prop as fn(Rgb) -> bool);
+    }
+
+    #[test]
+    fn test_quantize() {
+        fn prop(clr: Rgb) -> bool {
+            let yuv: YCbCr = ColorSpace::from_rgb(clr);
+            [8, 10, 12, 16].iter().all(|&bits| {
+                let lsb = 1. / (1u32 << bits) as f32;
+                [Range::Full, Range::Limited].iter().all(|&range| {
+                    let (y, cb, cr) = yuv.quantize(bits, range);
+                    let dq = YCbCr::dequantize(y, cb, cr, bits, range);
+                    dq.is_close_to(&yuv, lsb)
+                })
+            })
         }
         quickcheck(prop as fn(Rgb) -> bool);
     }
+
+    #[test]
+    fn test_dequantize_all_codes() {
+        let clip = |d: u16, lo: u16, hi: u16| -> u16 {
+            if d < lo { lo } else if d > hi { hi } else { d }
+        };
+        for bits in 8..11 {
+            let scale = 1u16 << (bits - 8);
+            for d in 0..(1u16 << bits) {
+                let full_c = if d == 0 { 1 } else { d };
+                let yuv = YCbCr::dequantize(d, full_c, full_c, bits, Range::Full);
+                assert_eq!(yuv.quantize(bits, Range::Full), (d, full_c, full_c));
+
+                let ly = clip(d, 16 * scale, 235 * scale);
+                let lc = clip(d, 16 * scale, 240 * scale);
+                let yuv = YCbCr::dequantize(d, d, d, bits, Range::Limited);
+                assert_eq!(yuv.quantize(bits, Range::Limited), (ly, lc, lc));
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice() {
+        fn prop(src: Vec<Rgb>) -> bool {
+            let mut yuv = vec![YCbCr::new(0., 0., 0.); src.len()];
+            let mut rgb = vec![Rgb::new(0., 0., 0.); src.len()];
+            YCbCr::from_rgb_slice(&src, &mut yuv);
+            YCbCr::to_rgb_slice(&yuv, &mut rgb);
+            src.iter().zip(yuv.iter().zip(rgb.iter())).all(|(&c, (y, r))| {
+                *y == YCbCr::from_rgb(c) && *r == y.to_rgb()
+            })
+        }
+        quickcheck(prop as fn(Vec<Rgb>) -> bool);
+    }
 }